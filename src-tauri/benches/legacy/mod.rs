@@ -0,0 +1,102 @@
+//! Per-skill scan loop from before ScanContext, kept as the benchmark baseline
+//! 引入 ScanContext 之前逐个 skill 加载配置的扫描循环，保留作为基准测试的基线
+//!
+//! 只保留旧实现中与耗时相关的 I/O 和解析步骤，结果不用于显示：
+//! 每个 skill 读取两次 SKILL.md 并各编译一次 Front Matter 正则，
+//! 重新读取 installed_plugins.json、known_marketplaces.json、marketplace 清单和设置文件。
+//! Keeps only the I/O and parsing steps of the old implementation that cost time, the results are not displayed:
+//! every skill reads SKILL.md twice and compiles the front matter regex for each read, then re-reads
+//! installed_plugins.json, known_marketplaces.json, the marketplace manifest and the settings file.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Files the old scan re-read for every skill
+/// 旧扫描对每个 skill 都重新读取的文件
+pub struct LegacyPaths {
+    pub plugins_dir: PathBuf,
+    pub settings: PathBuf,
+}
+
+/// Read and parse a JSON file, None when missing or invalid
+/// 读取并解析 JSON 文件，缺失或无效时返回 None
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// Old parse_skill_md: compiles the regex on every call
+/// 旧的 parse_skill_md：每次调用都编译正则
+fn parse_front_matter(content: &str) -> Option<serde_yaml::Value> {
+    let front_matter_regex = regex::Regex::new(r"^---\r?\n([\s\S]+?)\r?\n---").unwrap();
+    let raw = front_matter_regex.captures(content)?.get(1)?.as_str();
+    serde_yaml::from_str(raw).ok()
+}
+
+/// Old get_marketplace_name: reads the marketplace config, the manifest and the settings
+/// 旧的 get_marketplace_name：读取 marketplace 配置、清单和设置
+fn marketplace_name(paths: &LegacyPaths, marketplace_id: &str) -> Option<String> {
+    let _settings = read_json(&paths.settings);
+    let marketplaces = read_json(&paths.plugins_dir.join("known_marketplaces.json"))?;
+    let location = marketplaces.get(marketplace_id)?.get("installLocation")?.as_str()?;
+    let manifest = read_json(&Path::new(location).join(".claude-plugin").join("marketplace.json"))?;
+    manifest.get("name")?.as_str().map(str::to_string)
+}
+
+/// Old get_skill_marketplace: re-reads installed_plugins.json and matches by install path
+/// 旧的 get_skill_marketplace：重新读取 installed_plugins.json 并按安装目录匹配
+fn skill_marketplace(paths: &LegacyPaths, skill_path: &Path) -> Option<String> {
+    let installed = read_json(&paths.plugins_dir.join("installed_plugins.json"))?;
+    let skill_path = fs::canonicalize(skill_path).ok()?;
+
+    for (plugin_id, versions) in installed.get("plugins")?.as_object()? {
+        for version in versions.as_array()? {
+            let Some(install_path) = version.get("installPath").and_then(|path| path.as_str()) else {
+                continue;
+            };
+            let install_path = fs::canonicalize(install_path).unwrap_or_else(|_| PathBuf::from(install_path));
+            if skill_path.starts_with(&install_path) {
+                let _manifest = read_json(&install_path.join(".claude-plugin").join("plugin.json"));
+                let marketplace_id = plugin_id.split_once('@').map(|(_, id)| id)?;
+                return marketplace_name(paths, marketplace_id);
+            }
+        }
+    }
+
+    None
+}
+
+/// Old is_official_skill: reads SKILL.md a second time and re-parses it
+/// 旧的 is_official_skill：第二次读取 SKILL.md 并重新解析
+fn is_official_skill(paths: &LegacyPaths, skill_path: &Path) -> bool {
+    let _settings = read_json(&paths.settings);
+    fs::read_to_string(skill_path.join("SKILL.md"))
+        .ok()
+        .and_then(|content| parse_front_matter(&content))
+        .is_some_and(|front_matter| front_matter.get("official").is_some())
+}
+
+/// Scan skill folders the old way, returning how many skills were parsed
+/// 按旧方式扫描 skill 目录，返回解析的 skill 数量
+pub fn scan_skills(paths: &LegacyPaths, skills_dirs: &[PathBuf]) -> usize {
+    let mut count = 0;
+
+    for dir in skills_dirs {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let skill_path = entry.path();
+            let Ok(content) = fs::read_to_string(skill_path.join("SKILL.md")) else {
+                continue;
+            };
+
+            let _front_matter = parse_front_matter(&content);
+            let _marketplace = skill_marketplace(paths, &skill_path);
+            let _official = is_official_skill(paths, &skill_path);
+            count += 1;
+        }
+    }
+
+    count
+}
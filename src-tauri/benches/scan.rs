@@ -0,0 +1,162 @@
+//! Skill scan benchmark
+//! Skill 扫描基准测试
+//!
+//! 生成包含数百个 skills 和已安装插件的临时 Claude 目录，测量完整扫描耗时。
+//! 以前每个 skill 都会重新读取 installed_plugins.json 和 marketplace 清单，
+//! 耗时约为 `skills × context_load`；现在每次扫描只加载一次上下文。
+//! Generates a temporary Claude home with hundreds of skills and installed plugins and times a full scan.
+//! Previously every skill re-read installed_plugins.json and the marketplace manifests, costing roughly
+//! `skills × context_load`; the scan context is now loaded once per scan.
+//!
+//! `scan_skills_cold` 删除索引后重新解析所有 SKILL.md，`scan_skills_directory` 从索引读取未变化的 skills。
+//! `scan_skills_legacy` 是基线，运行 `legacy` 模块中保留的旧扫描循环。
+//! `scan_skills_cold` deletes the index and re-parses every SKILL.md, `scan_skills_directory` serves unchanged skills from the index.
+//! `scan_skills_legacy` is the baseline, running the old scan loop kept in the `legacy` module.
+
+mod legacy;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use skill_launcher_lib::context::ScanContext;
+use skill_launcher_lib::index::get_index_path;
+use skill_launcher_lib::skills::scan_skills;
+
+const USER_SKILLS: usize = 300;
+const PLUGINS: usize = 20;
+const SKILLS_PER_PLUGIN: usize = 5;
+
+/// Write a SKILL.md with a folded multi-line description
+/// 写入带多行折叠描述的 SKILL.md
+fn write_skill(dir: &Path, name: &str) {
+    fs::create_dir_all(dir).unwrap();
+    fs::write(
+        dir.join("SKILL.md"),
+        format!(
+            "---\nname: {name}\ndescription: >-\n  Benchmark skill {name}\n  spanning several lines.\nallowed-tools: Read, Grep\n---\n\n# {name}\n"
+        ),
+    )
+    .unwrap();
+}
+
+/// Build the benchmark fixture and return its home directory
+/// 构建基准测试数据并返回其 home 目录
+fn build_fixture() -> PathBuf {
+    let home = std::env::temp_dir().join("skill-launcher-bench");
+    let _ = fs::remove_dir_all(&home);
+
+    // Claude 目录不能位于项目的祖先目录中，否则会被当作项目的 .claude 再扫描一遍
+    // The Claude home must not sit in an ancestor of the project, or it is scanned again as the project's .claude
+    let claude_dir = claude_dir(&home);
+    for i in 0..USER_SKILLS {
+        let name = format!("user-skill-{i}");
+        write_skill(&claude_dir.join("skills").join(&name), &name);
+    }
+
+    let plugins_dir = claude_dir.join("plugins");
+    let marketplace_dir = plugins_dir.join("marketplaces").join("bench-marketplace");
+    fs::create_dir_all(marketplace_dir.join(".claude-plugin")).unwrap();
+    fs::write(
+        marketplace_dir.join(".claude-plugin").join("marketplace.json"),
+        r#"{"name": "bench-marketplace", "owner": {"name": "Bench"}, "plugins": []}"#,
+    )
+    .unwrap();
+    fs::write(
+        plugins_dir.join("known_marketplaces.json"),
+        serde_json::json!({
+            "bench-marketplace": { "installLocation": marketplace_dir.to_string_lossy() }
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let mut installed = serde_json::Map::new();
+    for p in 0..PLUGINS {
+        let plugin_name = format!("plugin-{p}");
+        let install_path = plugins_dir.join("cache").join(&plugin_name);
+        for s in 0..SKILLS_PER_PLUGIN {
+            let name = format!("skill-{s}");
+            write_skill(&install_path.join("skills").join(&name), &name);
+        }
+        installed.insert(
+            format!("{plugin_name}@bench-marketplace"),
+            serde_json::json!([{
+                "scope": "user",
+                "installPath": install_path.to_string_lossy(),
+                "version": "1.0.0",
+                "installedAt": "2025-01-01T00:00:00Z",
+                "lastUpdated": "2025-01-01T00:00:00Z",
+                "gitCommitSha": "0000000000000000000000000000000000000000"
+            }]),
+        );
+    }
+    fs::write(
+        plugins_dir.join("installed_plugins.json"),
+        serde_json::json!({ "version": 2, "plugins": installed }).to_string(),
+    )
+    .unwrap();
+
+    // 托管目录指向临时目录，避免读取本机的 /etc/claude-code / Point the managed dir into the fixture so the host's /etc/claude-code is not read
+    fs::write(
+        home.join("settings.json"),
+        serde_json::json!({ "managedConfigDir": home.join("managed") }).to_string(),
+    )
+    .unwrap();
+
+    fs::create_dir_all(project_dir(&home)).unwrap();
+    home
+}
+
+/// Claude home of the fixture
+/// 测试数据的 Claude 目录
+fn claude_dir(home: &Path) -> PathBuf {
+    home.join("claude-home").join(".claude")
+}
+
+/// Project directory of the fixture, in a sibling tree of the Claude home
+/// 测试数据的项目目录，与 Claude 目录位于不同的子树
+fn project_dir(home: &Path) -> PathBuf {
+    home.join("workspace").join("project")
+}
+
+fn bench_scan(c: &mut Criterion) {
+    let home = build_fixture();
+    std::env::set_var("CLAUDE_CONFIG_DIR", claude_dir(&home));
+    std::env::set_var("SKILL_LAUNCHER_SETTINGS", home.join("settings.json"));
+    std::env::set_var("SKILL_LAUNCHER_PROJECT_ROOT", project_dir(&home));
+    std::env::set_var("SKILL_LAUNCHER_INDEX", home.join("skill-index.json"));
+
+    c.bench_function("context_load", |b| b.iter(ScanContext::load));
+
+    c.bench_function("scan_skills_cold", |b| {
+        b.iter(|| {
+            let _ = fs::remove_file(get_index_path());
+            scan_skills(&ScanContext::load())
+        })
+    });
+
+    let plugins_dir = claude_dir(&home).join("plugins");
+    let legacy_paths = legacy::LegacyPaths {
+        plugins_dir: plugins_dir.clone(),
+        settings: home.join("settings.json"),
+    };
+    let legacy_dirs: Vec<PathBuf> = std::iter::once(claude_dir(&home).join("skills"))
+        .chain((0..PLUGINS).map(|p| plugins_dir.join("cache").join(format!("plugin-{p}")).join("skills")))
+        .collect();
+    assert_eq!(
+        legacy::scan_skills(&legacy_paths, &legacy_dirs),
+        USER_SKILLS + PLUGINS * SKILLS_PER_PLUGIN
+    );
+
+    c.bench_function("scan_skills_legacy", |b| {
+        b.iter(|| legacy::scan_skills(&legacy_paths, &legacy_dirs))
+    });
+
+    c.bench_function("scan_skills_directory", |b| {
+        b.iter(|| scan_skills(&ScanContext::load()))
+    });
+}
+
+criterion_group!(benches, bench_scan);
+criterion_main!(benches);
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+use crate::settings::LauncherSettings;
+
+/// Built-in Claude Code profile id
+/// 内置 Claude Code 配置 ID
+pub(crate) const CLAUDE_PROFILE: &str = "claude";
+
+/// Built-in Codex profile id
+/// 内置 Codex 配置 ID
+pub(crate) const CODEX_PROFILE: &str = "codex";
+
+/// Coding agent a skills directory belongs to, deciding how its skills are invoked
+/// skills 目录所属的编码代理，决定其 skills 的调用方式
+/// 模板中的 {name} 替换为 skill 名称 / {name} in the templates is replaced with the skill name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentProfile {
+    pub id: String,
+    /// 显示名称 / Display name
+    pub label: String,
+    /// 覆盖 skill 显示的范围标签，例如 "codex" / Overrides the scope tag shown on skills, e.g. "codex"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// 在代理会话中输入的文本，例如 "/{name}" / Text typed inside the agent session, e.g. "/{name}"
+    pub invocation: String,
+    /// 在终端中启动的命令，例如 "claude /{name}" / Command line run from a shell, e.g. "claude /{name}"
+    pub command: String,
+}
+
+impl AgentProfile {
+    /// Text typed inside the agent session to run a skill
+    /// 在代理会话中运行 skill 时输入的文本
+    pub fn invocation_for(&self, skill_name: &str) -> String {
+        self.invocation.replace("{name}", skill_name)
+    }
+
+    /// Shell command line that runs a skill
+    /// 在终端中运行 skill 的命令
+    pub fn command_for(&self, skill_name: &str) -> String {
+        self.command.replace("{name}", skill_name)
+    }
+}
+
+/// Built-in agent profiles, Claude Code first
+/// 内置代理配置，Claude Code 在前
+fn builtin_profiles() -> Vec<AgentProfile> {
+    vec![
+        AgentProfile {
+            id: CLAUDE_PROFILE.to_string(),
+            label: "Claude Code".to_string(),
+            tag: None,
+            invocation: "/{name}".to_string(),
+            command: "claude /{name}".to_string(),
+        },
+        AgentProfile {
+            id: CODEX_PROFILE.to_string(),
+            label: "Codex".to_string(),
+            tag: Some("codex".to_string()),
+            invocation: "${name}".to_string(),
+            command: "codex '${name}'".to_string(),
+        },
+    ]
+}
+
+/// Get agent profiles, user-defined profiles replacing built-ins with the same id
+/// 获取代理配置，用户定义的同 ID 配置替换内置配置
+/// 第一个始终是 Claude Code 配置 / The first profile is always the Claude Code one
+pub(crate) fn get_agent_profiles(settings: &LauncherSettings) -> Vec<AgentProfile> {
+    let mut profiles = builtin_profiles();
+
+    for profile in &settings.agent_profiles {
+        match profiles.iter_mut().find(|existing| existing.id == profile.id) {
+            Some(existing) => *existing = profile.clone(),
+            None => profiles.push(profile.clone()),
+        }
+    }
+
+    profiles
+}
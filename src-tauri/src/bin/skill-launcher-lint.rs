@@ -0,0 +1,7 @@
+// Console binary for CI: the GUI executable uses the Windows GUI subsystem in release builds,
+// so it cannot print to the terminal and shells do not wait for its exit code
+// 供 CI 使用的控制台程序：GUI 程序在 release 构建中使用 Windows GUI 子系统，无法输出到终端，shell 也不会等待其退出码
+
+fn main() {
+    std::process::exit(skill_launcher_lib::lint::run_lint_main(std::env::args().skip(1)));
+}
@@ -0,0 +1,121 @@
+use std::path::{Path, PathBuf};
+
+use crate::settings::{load_settings, LauncherSettings};
+
+/// Claude Code configuration directory (~/.claude by default)
+/// Claude Code 配置目录（默认为 ~/.claude）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaudeHome {
+    root: PathBuf,
+}
+
+/// Expand a leading ~ to the home directory
+/// 将开头的 ~ 展开为用户主目录
+pub(crate) fn expand_home(path: &str) -> PathBuf {
+    let rest = path
+        .strip_prefix("~/")
+        .or_else(|| path.strip_prefix("~\\"))
+        .or((path == "~").then_some(""));
+
+    match (rest, dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Get the organisation-managed Claude Code config directory
+/// 获取组织托管的 Claude Code 配置目录
+/// 可在设置中通过 managedConfigDir 覆盖 / Can be overridden with managedConfigDir in settings
+pub fn get_managed_dir(settings: &LauncherSettings) -> PathBuf {
+    if let Some(dir) = settings.managed_config_dir.as_deref().filter(|dir| !dir.is_empty()) {
+        return expand_home(dir);
+    }
+
+    #[cfg(windows)]
+    {
+        let program_data = std::env::var_os("ProgramData").unwrap_or_else(|| "C:\\ProgramData".into());
+        PathBuf::from(program_data).join("ClaudeCode")
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        PathBuf::from("/Library/Application Support/ClaudeCode")
+    }
+
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        PathBuf::from("/etc/claude-code")
+    }
+}
+
+impl ClaudeHome {
+    /// Use an explicit Claude config directory
+    /// 使用指定的 Claude 配置目录
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        ClaudeHome { root: root.into() }
+    }
+
+    /// Resolve the Claude config directory from the launcher settings file
+    /// 根据启动器设置文件解析 Claude 配置目录
+    pub fn resolve() -> Self {
+        Self::from_settings(&load_settings())
+    }
+
+    /// Resolve the Claude config directory
+    /// 解析 Claude 配置目录
+    /// 优先级：设置中的 claudeConfigDir，其次 CLAUDE_CONFIG_DIR 环境变量，最后 ~/.claude
+    /// Precedence: claudeConfigDir from settings, then CLAUDE_CONFIG_DIR, then ~/.claude
+    pub fn from_settings(settings: &LauncherSettings) -> Self {
+        if let Some(dir) = settings.claude_config_dir.as_deref().filter(|dir| !dir.is_empty()) {
+            return Self::new(expand_home(dir));
+        }
+
+        if let Some(dir) = std::env::var_os("CLAUDE_CONFIG_DIR").filter(|dir| !dir.is_empty()) {
+            return Self::new(dir);
+        }
+
+        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+        Self::new(home.join(".claude"))
+    }
+
+    /// Claude config directory
+    /// Claude 配置目录
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// User skills directory
+    /// 用户 skills 目录
+    pub fn skills_dir(&self) -> PathBuf {
+        self.root.join("skills")
+    }
+
+    /// Plugins directory
+    /// plugins 目录
+    pub fn plugins_dir(&self) -> PathBuf {
+        self.root.join("plugins")
+    }
+
+    /// User settings file (settings.json)
+    /// 用户设置文件（settings.json）
+    pub fn settings_path(&self) -> PathBuf {
+        self.root.join("settings.json")
+    }
+
+    /// Global Claude Code state file (.claude.json)
+    /// Claude Code 全局状态文件（.claude.json）
+    /// 位于配置目录内；默认的 ~/.claude 下不存在时则位于其上级（即 ~/.claude.json）
+    /// Lives inside the config dir, or for the default ~/.claude next to it (i.e. ~/.claude.json)
+    pub fn global_config_path(&self) -> PathBuf {
+        let inside = self.root.join(".claude.json");
+        if inside.exists() {
+            return inside;
+        }
+
+        let is_default = dirs::home_dir().is_some_and(|home| self.root == home.join(".claude"));
+        match self.root.parent().filter(|_| is_default) {
+            Some(parent) => parent.join(".claude.json"),
+            None => inside,
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::skills::get_project_root;
+
+/// Placeholders a command template may use
+/// 命令模板可使用的占位符
+pub const TEMPLATE_PLACEHOLDERS: &[&str] = &["task", "project_root", "git_branch", "selected_files", "clipboard"];
+
+/// Values only the frontend knows, passed in when a templated skill is launched
+/// 只有前端知道的值，在启动带模板的 skill 时传入
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateInput {
+    /// 用户在 skill 名称后输入的任务 / Task typed after the skill name
+    #[serde(default)]
+    pub task: Option<String>,
+    /// 剪贴板文本 / Clipboard text
+    #[serde(default)]
+    pub clipboard: Option<String>,
+    /// 选中的文件 / Selected files
+    #[serde(rename = "selectedFiles", default)]
+    pub selected_files: Vec<String>,
+}
+
+/// A piece of a parsed command template
+/// 解析后的命令模板片段
+#[derive(Debug, Clone)]
+enum Segment {
+    Text(String),
+    Placeholder(String),
+}
+
+/// Split a template into text and placeholders
+/// 将模板拆分为文本和占位符
+/// "{{" 和 "}}" 表示字面量花括号 / "{{" and "}}" stand for literal braces
+/// parameters: skill 声明的参数名，同样可作为占位符 / Parameter names declared by the skill, also usable as placeholders
+fn parse_template(template: &str, parameters: &[&str]) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => {
+                            return Err(format!(
+                                "占位符 {{{}缺少右花括号 / Placeholder {{{} is missing its closing brace",
+                                name, name
+                            ))
+                        }
+                    }
+                }
+
+                let name = name.trim().to_string();
+                if !TEMPLATE_PLACEHOLDERS.contains(&name.as_str()) && !parameters.contains(&name.as_str()) {
+                    let expected: Vec<&str> = TEMPLATE_PLACEHOLDERS.iter().chain(parameters).copied().collect();
+                    return Err(format!(
+                        "未知占位符 {{{}}}，可用的占位符：{} / Unknown placeholder {{{}}}, expected one of: {}",
+                        name,
+                        expected.join(", "),
+                        name,
+                        expected.join(", ")
+                    ));
+                }
+
+                if !text.is_empty() {
+                    segments.push(Segment::Text(std::mem::take(&mut text)));
+                }
+                segments.push(Segment::Placeholder(name));
+            }
+            '}' => {
+                return Err("多余的右花括号，字面量请写作 }} / Unmatched closing brace, write }} for a literal one".to_string())
+            }
+            c => text.push(c),
+        }
+    }
+
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+
+    Ok(segments)
+}
+
+/// Check a template for syntax errors and unknown placeholders
+/// 检查模板的语法错误和未知占位符
+pub(crate) fn validate_template(template: &str, parameters: &[&str]) -> Result<(), String> {
+    parse_template(template, parameters).map(|_| ())
+}
+
+/// Render a template, failing on unknown placeholders or placeholders without a value
+/// 渲染模板，遇到未知占位符或没有值的占位符时返回错误
+pub(crate) fn render_template(
+    template: &str,
+    values: &HashMap<&str, String>,
+    parameters: &[&str],
+) -> Result<String, String> {
+    let segments = parse_template(template, parameters)?;
+
+    let mut missing: Vec<&str> = segments
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Placeholder(name) if !values.contains_key(name.as_str()) => Some(name.as_str()),
+            _ => None,
+        })
+        .collect();
+    missing.sort();
+    missing.dedup();
+    if !missing.is_empty() {
+        let names = missing.iter().map(|name| format!("{{{}}}", name)).collect::<Vec<_>>().join(", ");
+        return Err(format!("占位符没有可用的值：{} / No value for placeholder: {}", names, names));
+    }
+
+    Ok(segments
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => text,
+            Segment::Placeholder(name) => values[name.as_str()].clone(),
+        })
+        .collect())
+}
+
+/// Locate the git directory of a repository, following a .git file in worktrees
+/// 定位仓库的 git 目录，worktree 中会跟随 .git 文件
+fn find_git_dir(project_root: &Path) -> Option<PathBuf> {
+    let dot_git = project_root.join(".git");
+    if dot_git.is_dir() {
+        return Some(dot_git);
+    }
+
+    let content = fs::read_to_string(&dot_git).ok()?;
+    let git_dir = content.strip_prefix("gitdir:")?.trim();
+    Some(project_root.join(git_dir))
+}
+
+/// Read the current branch from .git/HEAD, or the short commit id when detached
+/// 从 .git/HEAD 读取当前分支，分离头指针时返回短提交 ID
+pub(crate) fn read_git_branch(project_root: &Path) -> Option<String> {
+    let head = fs::read_to_string(find_git_dir(project_root)?.join("HEAD")).ok()?;
+    let head = head.trim();
+
+    match head.strip_prefix("ref:") {
+        Some(reference) => {
+            let reference = reference.trim();
+            Some(reference.strip_prefix("refs/heads/").unwrap_or(reference).to_string())
+        }
+        None => head.get(..7).map(str::to_string),
+    }
+}
+
+/// Collect placeholder values from the launch context
+/// 从启动上下文收集占位符的值
+/// project_root 与 git_branch 由后端解析，其余值由前端传入
+/// project_root and git_branch are resolved here, the other values come from the frontend
+pub(crate) fn context_values(input: TemplateInput) -> HashMap<&'static str, String> {
+    let mut values = HashMap::new();
+
+    if let Some(task) = input.task.filter(|task| !task.trim().is_empty()) {
+        values.insert("task", task);
+    }
+    if let Some(clipboard) = input.clipboard.filter(|clipboard| !clipboard.is_empty()) {
+        values.insert("clipboard", clipboard);
+    }
+    if !input.selected_files.is_empty() {
+        values.insert("selected_files", input.selected_files.join(" "));
+    }
+    if let Some(project_root) = get_project_root() {
+        if let Some(branch) = read_git_branch(&project_root) {
+            values.insert("git_branch", branch);
+        }
+        values.insert("project_root", project_root.to_string_lossy().to_string());
+    }
+
+    values
+}
+
+/// Render a skill's command template with the launch context
+/// 使用启动上下文渲染 skill 的命令模板
+#[tauri::command]
+pub fn render_skill_command(template: String, input: TemplateInput) -> Result<String, String> {
+    render_template(&template, &context_values(input), &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&'static str, &str)]) -> HashMap<&'static str, String> {
+        pairs.iter().map(|(name, value)| (*name, value.to_string())).collect()
+    }
+
+    #[test]
+    fn parse_template_splits_text_and_placeholders() {
+        let segments = parse_template("run {task} in { project_root }", &[]).unwrap();
+        let rendered: Vec<String> = segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => format!("T:{}", text),
+                Segment::Placeholder(name) => format!("P:{}", name),
+            })
+            .collect();
+        assert_eq!(rendered, ["T:run ", "P:task", "T: in ", "P:project_root"]);
+    }
+
+    #[test]
+    fn doubled_braces_are_literal() {
+        let rendered = render_template("{{json}} {{{task}}} }}", &values(&[("task", "x")]), &[]).unwrap();
+        assert_eq!(rendered, "{json} {x} }");
+    }
+
+    #[test]
+    fn unclosed_placeholder_is_an_error() {
+        let error = parse_template("claude /review {task", &[]).unwrap_err();
+        assert!(error.contains("{task"), "{}", error);
+    }
+
+    #[test]
+    fn unmatched_closing_brace_is_an_error() {
+        assert!(parse_template("claude }", &[]).is_err());
+    }
+
+    #[test]
+    fn unknown_placeholder_lists_the_expected_names() {
+        let error = parse_template("{ticket}", &["env"]).unwrap_err();
+        assert!(error.contains("{ticket}"), "{}", error);
+        assert!(error.contains("selected_files, clipboard, env"), "{}", error);
+    }
+
+    #[test]
+    fn parameters_are_accepted_as_placeholders() {
+        let rendered = render_template("/deploy {env}", &values(&[("env", "prod")]), &["env"]).unwrap();
+        assert_eq!(rendered, "/deploy prod");
+    }
+
+    #[test]
+    fn render_reports_every_missing_value_once() {
+        let error = render_template("{task} {clipboard} {task}", &HashMap::new(), &[]).unwrap_err();
+        assert!(error.contains("{clipboard}, {task} /"), "{}", error);
+    }
+
+    #[test]
+    fn selected_files_come_from_the_frontend() {
+        let input: TemplateInput = serde_json::from_str(r#"{"task":"fix","selectedFiles":["src/a.rs","src/b.rs"]}"#).unwrap();
+        let values = context_values(input);
+        let rendered = render_template("/review {selected_files} for {task}", &values, &[]).unwrap();
+        assert_eq!(rendered, "/review src/a.rs src/b.rs for fix");
+    }
+
+    #[test]
+    fn no_selected_files_is_a_missing_value() {
+        let values = context_values(TemplateInput::default());
+        let error = render_template("/review {selected_files}", &values, &[]).unwrap_err();
+        assert!(error.contains("{selected_files}"), "{}", error);
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::agent_profiles::{get_agent_profiles, AgentProfile};
+use crate::claude_home::ClaudeHome;
+use crate::marketplace::{read_marketplace_manifest, read_marketplaces_config, resolve_marketplace_name, MarketplaceConfig};
+use crate::plugins::{build_plugin_ref, canonical_path, read_installed_plugins, read_plugin_manifest, InstalledPluginsConfig, PluginRef};
+use crate::settings::{load_settings, LauncherSettings};
+
+/// Configuration loaded once per scan and shared by every skill
+/// 每次扫描只加载一次、供所有 skill 共享的配置
+pub struct ScanContext {
+    pub(crate) settings: LauncherSettings,
+    pub(crate) claude_home: ClaudeHome,
+    pub(crate) installed_plugins: InstalledPluginsConfig,
+    pub(crate) marketplaces: HashMap<String, MarketplaceConfig>,
+    /// 代理配置，第一个为 Claude Code / Agent profiles, Claude Code first
+    pub(crate) agent_profiles: Vec<AgentProfile>,
+    /// 规范化后的插件安装目录，按深度从深到浅排序 / Canonical plugin install paths, deepest first
+    plugin_installs: Vec<(PathBuf, PluginRef)>,
+    /// marketplace ID 到显示名称 / Marketplace id to display name
+    marketplace_names: HashMap<String, String>,
+}
+
+impl ScanContext {
+    /// Load settings, installed plugins and marketplace manifests
+    /// 加载设置、已安装插件和 marketplace 清单
+    pub fn load() -> Self {
+        let settings = load_settings();
+        let claude_home = ClaudeHome::from_settings(&settings);
+        let installed_plugins = read_installed_plugins(&claude_home);
+        let marketplaces = read_marketplaces_config(&claude_home);
+        let agent_profiles = get_agent_profiles(&settings);
+
+        let marketplace_names = marketplaces
+            .iter()
+            .map(|(marketplace_id, config)| {
+                let manifest = read_marketplace_manifest(Path::new(&config.install_location)).ok();
+                let name = resolve_marketplace_name(marketplace_id, manifest.as_ref(), &settings);
+                (marketplace_id.clone(), name)
+            })
+            .collect();
+
+        let mut plugin_installs: Vec<(PathBuf, PluginRef)> = installed_plugins
+            .plugins
+            .iter()
+            .flat_map(|(plugin_id, versions)| versions.iter().map(move |version| (plugin_id, version)))
+            .filter(|(_, version)| !version.install_path.is_empty())
+            .map(|(plugin_id, version)| {
+                let install_path = canonical_path(Path::new(&version.install_path));
+                let manifest = read_plugin_manifest(&install_path).unwrap_or_default();
+                let plugin = build_plugin_ref(plugin_id, version, &manifest);
+                (install_path, plugin)
+            })
+            .collect();
+        plugin_installs.sort_by_key(|(path, _)| std::cmp::Reverse(path.components().count()));
+
+        ScanContext {
+            settings,
+            claude_home,
+            installed_plugins,
+            marketplaces,
+            agent_profiles,
+            plugin_installs,
+            marketplace_names,
+        }
+    }
+
+    /// Get an agent profile by id, falling back to Claude Code for unknown ids
+    /// 按 ID 获取代理配置，未知 ID 回退到 Claude Code
+    pub(crate) fn agent_profile(&self, profile_id: &str) -> &AgentProfile {
+        self.agent_profiles
+            .iter()
+            .find(|profile| profile.id == profile_id)
+            .unwrap_or(&self.agent_profiles[0])
+    }
+
+    /// Get marketplace display name from marketplace ID
+    /// 从 marketplace ID 获取 marketplace 显示名称
+    pub(crate) fn marketplace_name(&self, marketplace_id: &str) -> String {
+        self.marketplace_names
+            .get(marketplace_id)
+            .cloned()
+            .unwrap_or_else(|| resolve_marketplace_name(marketplace_id, None, &self.settings))
+    }
+
+    /// Get the marketplace label for a plugin
+    /// 获取插件对应的 marketplace 显示名称
+    pub(crate) fn plugin_marketplace_label(&self, plugin: &PluginRef) -> String {
+        if plugin.marketplace_id.is_empty() {
+            "Local".to_string()
+        } else {
+            self.marketplace_name(&plugin.marketplace_id)
+        }
+    }
+
+    /// Find the installed plugin whose installPath contains the given skill path
+    /// 查找安装目录包含该 skill 路径的插件
+    /// 多个匹配时取最深的安装目录 / Picks the deepest install path when several match
+    pub(crate) fn find_plugin_for_path(&self, skill_path: &Path) -> Option<PluginRef> {
+        let skill_path = canonical_path(skill_path);
+
+        self.plugin_installs
+            .iter()
+            .find(|(install_path, _)| skill_path.starts_with(install_path))
+            .map(|(_, plugin)| plugin.clone())
+    }
+}
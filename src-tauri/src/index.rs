@@ -0,0 +1,359 @@
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+use crate::settings::APP_IDENTIFIER;
+use crate::skills::{read_skill_file, scan_in_background, ParsedSkill, ScanResult};
+
+/// Index format version, bump whenever the index or ParsedSkill layout changes
+/// 索引格式版本，索引或 ParsedSkill 结构变化时需要递增
+const INDEX_VERSION: u32 = 3;
+
+/// A cached SKILL.md parse result
+/// 缓存的 SKILL.md 解析结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexEntry {
+    /// SKILL.md 修改时间（UNIX 纪元起的纳秒数）/ SKILL.md mtime in nanoseconds since the UNIX epoch
+    mtime: u64,
+    /// SKILL.md 文件大小 / SKILL.md size in bytes
+    size: u64,
+    skill: ParsedSkill,
+}
+
+/// Outcome of checking one skill directory against the index
+/// 对照索引检查单个 skill 目录的结果
+enum Lookup {
+    /// SKILL.md 未变化，使用缓存 / SKILL.md unchanged, served from the index
+    Cached(ParsedSkill),
+    /// SKILL.md 已变化，重新解析 / SKILL.md changed and was re-parsed
+    Parsed { mtime: u64, size: u64, skill: Option<ParsedSkill> },
+    /// 没有 SKILL.md / No SKILL.md
+    Missing,
+}
+
+/// On-disk skill index keyed by skill directory path
+/// 以 skill 目录路径为键的磁盘 skill 索引
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct SkillIndex {
+    version: u32,
+    entries: HashMap<String, IndexEntry>,
+    /// 本次扫描访问过的目录 / Directories visited during this scan
+    #[serde(skip)]
+    visited: HashSet<String>,
+    /// 本次扫描的 skills 目录，只清理其下的条目 / Skills directories scanned in this pass, only entries below them are pruned
+    #[serde(skip)]
+    roots: Vec<PathBuf>,
+    /// 索引是否需要写回磁盘 / Whether the index must be written back
+    #[serde(skip)]
+    dirty: bool,
+}
+
+/// Get skill index file path
+/// 获取 skill 索引文件路径
+/// 位于应用本地数据目录，可通过 SKILL_LAUNCHER_INDEX 环境变量覆盖
+/// Lives in the app's local data dir; can be overridden with SKILL_LAUNCHER_INDEX
+pub fn get_index_path() -> PathBuf {
+    if let Ok(path) = std::env::var("SKILL_LAUNCHER_INDEX") {
+        return PathBuf::from(path);
+    }
+
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_IDENTIFIER)
+        .join("skill-index.json")
+}
+
+/// Serialises index saves within the process, the UI scan and the file watcher run on different threads
+/// 串行化进程内的索引保存，界面扫描和文件监听器运行在不同线程上
+static SAVE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Counter making temp file names unique within the process
+/// 使进程内临时文件名唯一的计数器
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Get mtime and size of a skill's SKILL.md (or skill.md)
+/// 获取 skill 的 SKILL.md（或 skill.md）的修改时间和大小
+fn file_stamp(skill_path: &Path) -> Option<(u64, u64)> {
+    let metadata = fs::metadata(skill_path.join("SKILL.md"))
+        .or_else(|_| fs::metadata(skill_path.join("skill.md")))
+        .ok()?;
+
+    let mtime = metadata
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_nanos() as u64;
+
+    Some((mtime, metadata.len()))
+}
+
+impl SkillIndex {
+    /// Load the index, starting empty when it is missing, invalid or from another version
+    /// 读取索引，文件缺失、无效或版本不一致时从空索引开始
+    pub(crate) fn load() -> Self {
+        Self::load_from(&get_index_path())
+    }
+
+    /// Load the index from a given file
+    /// 从指定文件读取索引
+    fn load_from(index_path: &Path) -> Self {
+
+        let Ok(content) = fs::read_to_string(index_path) else {
+            return SkillIndex::default();
+        };
+
+        match serde_json::from_str::<SkillIndex>(&content) {
+            Ok(index) if index.version == INDEX_VERSION => index,
+            Ok(index) => {
+                println!(
+                    "🔄 索引版本 {} 已过期，重新建立 / Index version {} is stale, rebuilding",
+                    index.version, index.version
+                );
+                SkillIndex::default()
+            }
+            Err(e) => {
+                eprintln!("⚠️ 索引文件无效 / Invalid index file {}: {}", index_path.display(), e);
+                SkillIndex::default()
+            }
+        }
+    }
+
+    /// Empty index that is always written back, used to rebuild from scratch
+    /// 总会写回磁盘的空索引，用于从头重建
+    pub(crate) fn empty() -> Self {
+        SkillIndex {
+            dirty: true,
+            ..SkillIndex::default()
+        }
+    }
+
+    /// Record a skills directory scanned in this pass
+    /// 记录本次扫描的 skills 目录
+    pub(crate) fn add_root(&mut self, root: &Path) {
+        self.roots.push(root.to_path_buf());
+    }
+
+    /// Whether an entry lies below a skills directory scanned in this pass
+    /// 条目是否位于本次扫描的 skills 目录下
+    fn is_scanned(&self, key: &str) -> bool {
+        let path = Path::new(key);
+        self.roots.iter().any(|root| path.starts_with(root))
+    }
+
+    /// Return the parsed skills, re-parsing in parallel only those whose SKILL.md mtime or size changed
+    /// 返回解析后的 skills，仅并行重新解析 SKILL.md 修改时间或大小变化的条目
+    pub(crate) fn parse_all(&mut self, skill_paths: &[PathBuf]) -> Vec<Option<ParsedSkill>> {
+        let lookups: Vec<(String, Lookup)> = skill_paths
+            .par_iter()
+            .map(|skill_path| {
+                let key = skill_path.to_string_lossy().to_string();
+                let Some((mtime, size)) = file_stamp(skill_path) else {
+                    return (key, Lookup::Missing);
+                };
+
+                if let Some(entry) = self.entries.get(&key) {
+                    if entry.mtime == mtime && entry.size == size {
+                        return (key, Lookup::Cached(entry.skill.clone()));
+                    }
+                }
+
+                let skill = read_skill_file(skill_path);
+                (key, Lookup::Parsed { mtime, size, skill })
+            })
+            .collect();
+
+        lookups
+            .into_iter()
+            .map(|(key, lookup)| {
+                self.visited.insert(key.clone());
+                match lookup {
+                    Lookup::Cached(skill) => Some(skill),
+                    Lookup::Parsed { mtime, size, skill: Some(skill) } => {
+                        self.entries.insert(key, IndexEntry { mtime, size, skill: skill.clone() });
+                        self.dirty = true;
+                        Some(skill)
+                    }
+                    Lookup::Parsed { skill: None, .. } | Lookup::Missing => {
+                        self.dirty |= self.entries.remove(&key).is_some();
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Drop entries that vanished from the scanned directories and write the index if anything changed
+    /// 删除已从本次扫描目录中消失的条目，有变化时写回索引
+    /// 索引由所有项目的启动器共享：写入前合并磁盘上其他目录的条目，不清理未扫描的目录
+    /// The index is shared by every project's launcher: entries of other directories are merged from disk and never pruned
+    pub(crate) fn save(self) {
+        self.save_to(&get_index_path());
+    }
+
+    /// Save the index to a given file
+    /// 将索引保存到指定文件
+    fn save_to(mut self, index_path: &Path) {
+        let count = self.entries.len();
+        let visited = std::mem::take(&mut self.visited);
+        let mut entries = std::mem::take(&mut self.entries);
+        entries.retain(|key, _| visited.contains(key) || !self.is_scanned(key));
+        if !self.dirty && entries.len() == count {
+            return;
+        }
+
+        // 合并与写入之间不能插入其他保存 / No other save may run between the merge and the write
+        let _guard = SAVE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        // 保留其他实例在本次扫描期间写入的条目 / Keep entries other instances wrote during this scan
+        for (key, entry) in Self::load_from(index_path).entries {
+            if !self.is_scanned(&key) {
+                entries.entry(key).or_insert(entry);
+            }
+        }
+        self.entries = entries;
+        self.version = INDEX_VERSION;
+
+        let content = match serde_json::to_string(&self) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("⚠️ 序列化索引失败 / Failed to serialize index: {}", e);
+                return;
+            }
+        };
+
+        if let Some(parent) = index_path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                eprintln!("⚠️ 无法创建索引目录 / Failed to create index directory: {}", e);
+                return;
+            }
+        }
+
+        // 先写临时文件再替换，避免中断时留下半个索引；文件名按进程和次数区分，并发写入互不干扰
+        // Write then rename so an interrupted save never leaves half an index; the temp name is unique per process
+        // and save so concurrent writers never interleave
+        let temp_path = index_path.with_extension(format!(
+            "json.{}.{}.tmp",
+            std::process::id(),
+            TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        if let Err(e) = fs::write(&temp_path, content).and_then(|_| fs::rename(&temp_path, index_path)) {
+            let _ = fs::remove_file(&temp_path);
+            eprintln!("⚠️ 无法写入索引 / Failed to write index {}: {}", index_path.display(), e);
+        }
+    }
+}
+
+/// Discard the skill index and rescan every skill from disk
+/// 丢弃 skill 索引并从磁盘重新扫描所有 skills
+#[tauri::command]
+pub async fn rebuild_index(app: tauri::AppHandle) -> Result<ScanResult, String> {
+    println!("🔄 重建 skill 索引 / Rebuilding skill index");
+    scan_in_background(app, SkillIndex::empty).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_skill(root: &Path, name: &str) -> PathBuf {
+        let dir = root.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("SKILL.md"), format!("---\nname: {name}\ndescription: Test\n---\n")).unwrap();
+        dir
+    }
+
+    /// Scan one skills directory into the index file, like one launcher instance would
+    /// 像单个启动器实例一样，把一个 skills 目录扫描进索引文件
+    fn scan_root(index_path: &Path, root: &Path) {
+        let mut index = SkillIndex::load_from(index_path);
+        index.add_root(root);
+        let skill_paths: Vec<PathBuf> = fs::read_dir(root).unwrap().map(|entry| entry.unwrap().path()).collect();
+        index.parse_all(&skill_paths);
+        index.save_to(index_path);
+    }
+
+    fn keys(index_path: &Path) -> Vec<String> {
+        let mut keys: Vec<String> = SkillIndex::load_from(index_path).entries.into_keys().collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn saves_from_different_roots_keep_each_others_entries() {
+        let temp = tempfile::tempdir().unwrap();
+        let index_path = temp.path().join("skill-index.json");
+        let first = temp.path().join("first");
+        let second = temp.path().join("second");
+        let a = write_skill(&first, "a");
+        let b = write_skill(&second, "b");
+
+        scan_root(&index_path, &first);
+        scan_root(&index_path, &second);
+        scan_root(&index_path, &first);
+
+        let expected = [a.to_string_lossy().to_string(), b.to_string_lossy().to_string()];
+        assert_eq!(keys(&index_path), expected);
+    }
+
+    #[test]
+    fn removed_skills_are_pruned_only_under_scanned_roots() {
+        let temp = tempfile::tempdir().unwrap();
+        let index_path = temp.path().join("skill-index.json");
+        let first = temp.path().join("first");
+        let second = temp.path().join("second");
+        let a = write_skill(&first, "a");
+        let gone = write_skill(&first, "gone");
+        let b = write_skill(&second, "b");
+
+        scan_root(&index_path, &first);
+        scan_root(&index_path, &second);
+        fs::remove_dir_all(&gone).unwrap();
+        scan_root(&index_path, &first);
+
+        let expected = [a.to_string_lossy().to_string(), b.to_string_lossy().to_string()];
+        assert_eq!(keys(&index_path), expected);
+    }
+
+    #[test]
+    fn concurrent_saves_keep_every_root() {
+        let temp = tempfile::tempdir().unwrap();
+        let index_path = temp.path().join("skill-index.json");
+        let roots: Vec<PathBuf> = (0..8).map(|i| temp.path().join(format!("root-{i}"))).collect();
+        for root in &roots {
+            write_skill(root, "a");
+        }
+
+        std::thread::scope(|scope| {
+            for root in &roots {
+                let index_path = &index_path;
+                scope.spawn(move || scan_root(index_path, root));
+            }
+        });
+
+        assert_eq!(keys(&index_path).len(), roots.len());
+    }
+
+    #[test]
+    fn save_leaves_no_temp_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let index_path = temp.path().join("skill-index.json");
+        let root = temp.path().join("skills");
+        write_skill(&root, "a");
+
+        scan_root(&index_path, &root);
+        scan_root(&index_path, &root);
+
+        let files: Vec<String> = fs::read_dir(temp.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .filter(|name| name.ends_with(".tmp"))
+            .collect();
+        assert!(files.is_empty(), "{:?}", files);
+    }
+}
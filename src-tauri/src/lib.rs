@@ -1,9 +1,24 @@
 use std::fs;
-use std::path::PathBuf;
 use std::sync::Mutex;
 use tauri::Manager;
 
+pub mod agent_profiles;
+pub mod claude_home;
+pub mod command_template;
+pub mod context;
+pub mod index;
+pub mod lint;
+pub mod marketplace;
+pub mod mcp;
+pub mod official;
+pub mod plugins;
+pub mod settings;
+pub mod skill_parameters;
 pub mod skills;
+pub mod slash_commands;
+#[cfg(test)]
+mod test_support;
+pub mod watcher;
 
 pub use skills::*;
 
@@ -115,8 +130,7 @@ fn setup_claude_skill() -> Result<(), String> {
 
     const SKILL_MD_CONTENT: &str = include_str!("../../skills/skill-launcher/skill.md");
 
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    let skills_dir = home.join(".claude").join("skills");
+    let skills_dir = claude_home::ClaudeHome::resolve().skills_dir();
     let skill_dir = skills_dir.join("skill-launcher");
 
     let skill_md = skill_dir.join("SKILL.md");
@@ -295,6 +309,8 @@ pub fn run() {
     // 解析命令行参数
     let mut project_root: Option<String> = None;
     let mut target_hwnd: Option<i64> = None;
+    let mut lint_mode = false;
+    let mut json_output = false;
 
     let args: Vec<String> = std::env::args().collect();
     let mut i = 1;
@@ -316,12 +332,29 @@ pub fn run() {
                 target_hwnd = arg[14..].parse::<i64>().ok();
                 i += 1;
             }
+            "--lint" => {
+                lint_mode = true;
+                i += 1;
+            }
+            "--json" => {
+                json_output = true;
+                i += 1;
+            }
             _ => {
                 i += 1;
             }
         }
     }
 
+    // Headless lint mode for CI, no window is created
+    // 用于 CI 的无界面检查模式，不创建窗口
+    if lint_mode {
+        if let Some(ref root) = project_root {
+            std::env::set_var("SKILL_LAUNCHER_PROJECT_ROOT", root);
+        }
+        std::process::exit(lint::run_lint_cli(json_output));
+    }
+
     // Check if this project already has a window open
     // 检查该项目的窗口是否已打开
     if let Some(ref root) = project_root {
@@ -353,11 +386,18 @@ pub fn run() {
         .manage(TargetWindowState {
             hwnd: Mutex::new(target_hwnd),
         })
+        .manage(watcher::KnownSkills::default())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_fs::init())
         .invoke_handler(tauri::generate_handler![
             health_check,
             skills::scan_skills_directory,
+            index::rebuild_index,
+            lint::lint_skills,
+            marketplace::list_marketplaces,
+            mcp::list_mcp_servers,
+            command_template::render_skill_command,
+            skill_parameters::build_skill_command,
             send_to_claude_cli,
             get_project_root,
         ])
@@ -376,6 +416,12 @@ pub fn run() {
                 std::env::set_var("SKILL_LAUNCHER_PROJECT_ROOT", root);
             }
 
+            // Watch skill directories so the list updates live
+            // 监听 skills 目录，实时更新列表
+            if let Err(e) = watcher::start_skill_watcher(app.handle().clone()) {
+                eprintln!("⚠️ Warning: Failed to start skill watcher: {}", e);
+            }
+
             // Auto-configure Claude Code skill on first run
             // 首次运行时自动配置 Claude Code skill
             if let Err(e) = setup_claude_skill() {
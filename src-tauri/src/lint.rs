@@ -0,0 +1,536 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::claude_home::ClaudeHome;
+use crate::command_template::validate_template;
+use crate::settings::load_settings;
+use crate::skill_parameters::parse_parameters;
+use crate::skills::{
+    extract_front_matter, find_skill_dirs, get_skill_directories, has_skill_md, SkillFrontMatter,
+    DEFAULT_SKILL_SEARCH_DEPTH,
+};
+
+/// Maximum length of a skill name
+/// skill 名称的最大长度
+const MAX_NAME_LENGTH: usize = 64;
+
+/// Maximum length of a skill description
+/// skill 描述的最大长度
+const MAX_DESCRIPTION_LENGTH: usize = 1024;
+
+/// Diagnostic severity
+/// 诊断级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+}
+
+impl std::fmt::Display for LintSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LintSeverity::Error => write!(f, "error"),
+            LintSeverity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A single lint finding for a SKILL.md file
+/// SKILL.md 文件的单条检查结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintDiagnostic {
+    pub severity: LintSeverity,
+    /// 规则标识，例如 "missing-name" / Rule id, e.g. "missing-name"
+    pub code: String,
+    pub message: String,
+    pub path: String,
+    /// 从 1 开始的行号 / 1-based line number
+    pub line: Option<usize>,
+}
+
+/// Lint results for one skill directory
+/// 单个 skill 目录的检查结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillLintReport {
+    pub skill: String,
+    pub path: String,
+    pub diagnostics: Vec<LintDiagnostic>,
+}
+
+impl SkillLintReport {
+    fn push(&mut self, severity: LintSeverity, code: &str, message: String, path: &Path, line: Option<usize>) {
+        self.diagnostics.push(LintDiagnostic {
+            severity,
+            code: code.to_string(),
+            message,
+            path: path.to_string_lossy().to_string(),
+            line,
+        });
+    }
+
+    /// Whether any diagnostic is an error
+    /// 是否包含错误级别的诊断
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == LintSeverity::Error)
+    }
+}
+
+/// Find the 1-based line of a top-level front matter key
+/// 查找顶层 Front Matter 键所在的行号（从 1 开始）
+fn find_key_line(content: &str, key: &str) -> Option<usize> {
+    content
+        .lines()
+        .enumerate()
+        .skip(1)
+        .take_while(|(_, line)| line.trim_end() != "---")
+        .find(|(_, line)| {
+            line.strip_prefix(key)
+                .is_some_and(|rest| rest.trim_start().starts_with(':'))
+        })
+        .map(|(index, _)| index + 1)
+}
+
+/// Check that a skill name only uses lowercase letters, digits and hyphens
+/// 检查 skill 名称是否只包含小写字母、数字和连字符
+fn is_valid_skill_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+        && !name.contains("--")
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// Lint a single skill directory
+/// 检查单个 skill 目录
+pub fn lint_skill(skill_path: &Path) -> SkillLintReport {
+    let dir_name = skill_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut report = SkillLintReport {
+        skill: dir_name.clone(),
+        path: skill_path.to_string_lossy().to_string(),
+        diagnostics: Vec::new(),
+    };
+
+    // 尝试读取 SKILL.md 或 skill.md / Try to read SKILL.md or skill.md
+    let readme_path = [skill_path.join("SKILL.md"), skill_path.join("skill.md")]
+        .into_iter()
+        .find(|path| path.is_file());
+
+    let Some(readme_path) = readme_path else {
+        report.push(
+            LintSeverity::Warning,
+            "missing-skill-md",
+            "目录中没有 SKILL.md / Directory has no SKILL.md".to_string(),
+            skill_path,
+            None,
+        );
+        return report;
+    };
+
+    let content = match fs::read_to_string(&readme_path) {
+        Ok(content) => content,
+        Err(e) => {
+            report.push(
+                LintSeverity::Error,
+                "unreadable-file",
+                format!("无法读取文件 / Unable to read file: {}", e),
+                &readme_path,
+                None,
+            );
+            return report;
+        }
+    };
+
+    let Some(raw) = extract_front_matter(&content) else {
+        report.push(
+            LintSeverity::Error,
+            "missing-front-matter",
+            "缺少 Front Matter / Missing front matter".to_string(),
+            &readme_path,
+            Some(1),
+        );
+        return report;
+    };
+
+    let front_matter = match serde_yaml::from_str::<Option<SkillFrontMatter>>(raw) {
+        Ok(front_matter) => front_matter.unwrap_or_default(),
+        Err(e) => {
+            // Front Matter 从第 2 行开始 / Front matter starts on line 2
+            let line = e.location().map(|location| location.line() + 1);
+            report.push(
+                LintSeverity::Error,
+                "invalid-yaml",
+                format!("Front Matter 不是有效的 YAML / Invalid YAML front matter: {}", e),
+                &readme_path,
+                line.or(Some(1)),
+            );
+            return report;
+        }
+    };
+
+    match front_matter.name.as_deref() {
+        None => report.push(
+            LintSeverity::Error,
+            "missing-name",
+            "缺少 name 字段 / Missing `name`".to_string(),
+            &readme_path,
+            Some(1),
+        ),
+        Some(name) => {
+            let line = find_key_line(&content, "name");
+            if !is_valid_skill_name(name) || name.len() > MAX_NAME_LENGTH {
+                report.push(
+                    LintSeverity::Error,
+                    "invalid-name",
+                    format!(
+                        "name \"{}\" 只能包含小写字母、数字和连字符，且不超过 {} 个字符 / must use lowercase letters, digits and hyphens and be at most {} characters",
+                        name, MAX_NAME_LENGTH, MAX_NAME_LENGTH
+                    ),
+                    &readme_path,
+                    line,
+                );
+            }
+            if name != dir_name {
+                report.push(
+                    LintSeverity::Error,
+                    "name-mismatch",
+                    format!(
+                        "name \"{}\" 与目录名 \"{}\" 不一致 / does not match directory name",
+                        name, dir_name
+                    ),
+                    &readme_path,
+                    line,
+                );
+            }
+        }
+    }
+
+    match front_matter.description.as_deref() {
+        None => report.push(
+            LintSeverity::Error,
+            "missing-description",
+            "缺少 description 字段 / Missing `description`".to_string(),
+            &readme_path,
+            Some(1),
+        ),
+        Some(description) => {
+            let length = description.chars().count();
+            if length > MAX_DESCRIPTION_LENGTH {
+                report.push(
+                    LintSeverity::Error,
+                    "description-too-long",
+                    format!(
+                        "description 长度为 {}，超过上限 {} / description is {} characters, limit is {}",
+                        length, MAX_DESCRIPTION_LENGTH, length, MAX_DESCRIPTION_LENGTH
+                    ),
+                    &readme_path,
+                    find_key_line(&content, "description"),
+                );
+            }
+        }
+    }
+
+    // 扫描时会跳过无效的参数定义，这里报告出来 / Scans skip invalid parameter entries, report them here
+    let raw_parameters = serde_yaml::from_str::<serde_yaml::Value>(raw)
+        .ok()
+        .and_then(|value| value.get("parameters").cloned());
+    if let Some(raw_parameters) = raw_parameters {
+        for error in parse_parameters(raw_parameters).1 {
+            report.push(
+                LintSeverity::Warning,
+                "invalid-parameter",
+                error,
+                &readme_path,
+                find_key_line(&content, "parameters"),
+            );
+        }
+    }
+
+    if let Some(template) = front_matter.command_template.as_deref() {
+        let parameters: Vec<&str> = front_matter
+            .parameters
+            .iter()
+            .map(|parameter| parameter.name.as_str())
+            .collect();
+        if let Err(e) = validate_template(template, &parameters) {
+            report.push(
+                LintSeverity::Error,
+                "invalid-command-template",
+                format!("command-template 无效 / Invalid command-template: {}", e),
+                &readme_path,
+                find_key_line(&content, "command-template"),
+            );
+        }
+    }
+
+    report
+}
+
+/// Lint every skill in the ordered skill directories
+/// 检查所有 skills 目录中的每个 skill
+pub fn lint_all_skills() -> Vec<SkillLintReport> {
+    let mut reports = Vec::new();
+    let settings = load_settings();
+    let max_depth = settings
+        .skill_search_depth
+        .unwrap_or(DEFAULT_SKILL_SEARCH_DEPTH);
+
+    let claude_home = ClaudeHome::from_settings(&settings);
+
+    for root in get_skill_directories(&settings, &claude_home) {
+        let dir = root.path;
+        if !dir.exists() {
+            if root.configured {
+                let mut report = SkillLintReport {
+                    skill: String::new(),
+                    path: dir.to_string_lossy().to_string(),
+                    diagnostics: Vec::new(),
+                };
+                report.push(
+                    LintSeverity::Warning,
+                    "missing-skills-dir",
+                    "配置的 skills 目录不存在 / Configured skills directory does not exist".to_string(),
+                    &dir,
+                    None,
+                );
+                reports.push(report);
+            }
+            continue;
+        }
+
+        let mut on_error = |path: &Path, message: String| {
+            let mut report = SkillLintReport {
+                skill: String::new(),
+                path: path.to_string_lossy().to_string(),
+                diagnostics: Vec::new(),
+            };
+            report.push(LintSeverity::Error, "unreadable-file", message, path, None);
+            reports.push(report);
+        };
+        let skill_dirs = find_skill_dirs(&dir, max_depth, &mut on_error);
+
+        // 没有 SKILL.md 的目录（如 assets、scripts）不是 skill / Folders without SKILL.md (assets, scripts) are not skills
+        for skill_dir in skill_dirs.into_iter().filter(|skill_dir| has_skill_md(&skill_dir.path)) {
+            reports.push(lint_skill(&skill_dir.path));
+        }
+    }
+
+    reports
+}
+
+/// Lint all skills and return per-skill diagnostics
+/// 检查所有 skills 并返回每个 skill 的诊断信息
+#[tauri::command]
+pub fn lint_skills() -> Vec<SkillLintReport> {
+    lint_all_skills()
+}
+
+/// Parse lint arguments (--project-root, --json) and run the lint
+/// 解析检查参数（--project-root、--json）并执行检查
+pub fn run_lint_main(args: impl Iterator<Item = String>) -> i32 {
+    let mut json = false;
+    let mut args = args.peekable();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--project-root" => {
+                if let Some(root) = args.next() {
+                    std::env::set_var("SKILL_LAUNCHER_PROJECT_ROOT", root);
+                }
+            }
+            arg if arg.starts_with("--project-root=") => {
+                std::env::set_var("SKILL_LAUNCHER_PROJECT_ROOT", &arg["--project-root=".len()..]);
+            }
+            // 兼容 GUI 程序的 --lint 写法 / Accept the GUI executable's --lint flag
+            "--lint" => {}
+            arg => {
+                eprintln!("未知参数 / Unknown argument: {}", arg);
+                return 2;
+            }
+        }
+    }
+
+    run_lint_cli(json)
+}
+
+/// Headless lint mode: print diagnostics and return the process exit code
+/// 无界面检查模式：打印诊断信息并返回进程退出码
+pub fn run_lint_cli(json: bool) -> i32 {
+    let reports = lint_all_skills();
+    let failed = reports.iter().any(SkillLintReport::has_errors);
+
+    if json {
+        match serde_json::to_string_pretty(&reports) {
+            Ok(output) => println!("{}", output),
+            Err(e) => {
+                eprintln!("序列化检查结果失败 / Failed to serialize lint results: {}", e);
+                return 2;
+            }
+        }
+    } else {
+        for diagnostic in reports.iter().flat_map(|report| report.diagnostics.iter()) {
+            match diagnostic.line {
+                Some(line) => println!(
+                    "{}:{}: {}[{}]: {}",
+                    diagnostic.path, line, diagnostic.severity, diagnostic.code, diagnostic.message
+                ),
+                None => println!(
+                    "{}: {}[{}]: {}",
+                    diagnostic.path, diagnostic.severity, diagnostic.code, diagnostic.message
+                ),
+            }
+        }
+        println!(
+            "已检查 {} 个 skills / Linted {} skills",
+            reports.len(),
+            reports.len()
+        );
+    }
+
+    if failed {
+        1
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestEnv;
+    use std::path::PathBuf;
+
+    fn write_skill(root: &Path, dir_name: &str, content: &str) -> PathBuf {
+        let dir = root.join(dir_name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("SKILL.md"), content).unwrap();
+        dir
+    }
+
+    fn codes(report: &SkillLintReport) -> Vec<&str> {
+        report.diagnostics.iter().map(|d| d.code.as_str()).collect()
+    }
+
+    fn lint(dir_name: &str, content: &str) -> SkillLintReport {
+        let temp = tempfile::tempdir().unwrap();
+        lint_skill(&write_skill(temp.path(), dir_name, content))
+    }
+
+    #[test]
+    fn valid_skill_has_no_diagnostics() {
+        let report = lint("deploy", "---\nname: deploy\ndescription: Deploy things\n---\n");
+        assert!(report.diagnostics.is_empty(), "{:?}", report.diagnostics);
+        assert!(!report.has_errors());
+    }
+
+    #[test]
+    fn missing_skill_md_is_a_warning() {
+        let temp = tempfile::tempdir().unwrap();
+        let report = lint_skill(temp.path());
+        assert_eq!(codes(&report), ["missing-skill-md"]);
+        assert!(!report.has_errors());
+    }
+
+    #[test]
+    fn missing_name_and_description() {
+        let report = lint("deploy", "---\nversion: 1.0\n---\n");
+        assert_eq!(codes(&report), ["missing-name", "missing-description"]);
+    }
+
+    #[test]
+    fn invalid_name_is_reported_on_its_line() {
+        let report = lint("Deploy_Skill", "---\ndescription: Deploy\nname: Deploy_Skill\n---\n");
+        assert_eq!(codes(&report), ["invalid-name"]);
+        assert_eq!(report.diagnostics[0].line, Some(3));
+    }
+
+    #[test]
+    fn name_must_match_the_directory() {
+        let report = lint("deploy", "---\nname: release\ndescription: Deploy\n---\n");
+        assert_eq!(codes(&report), ["name-mismatch"]);
+        assert_eq!(report.diagnostics[0].line, Some(2));
+    }
+
+    #[test]
+    fn description_too_long() {
+        let content = format!("---\nname: deploy\ndescription: {}\n---\n", "x".repeat(MAX_DESCRIPTION_LENGTH + 1));
+        let report = lint("deploy", &content);
+        assert_eq!(codes(&report), ["description-too-long"]);
+        assert_eq!(report.diagnostics[0].line, Some(3));
+    }
+
+    #[test]
+    fn invalid_yaml_maps_to_the_file_line() {
+        let report = lint("deploy", "---\nname: deploy\ndescription: Deploy: now\nversion: 1\n---\n");
+        assert_eq!(codes(&report), ["invalid-yaml"]);
+        assert_eq!(report.diagnostics[0].line, Some(3));
+    }
+
+    #[test]
+    fn invalid_command_template() {
+        let report = lint(
+            "deploy",
+            "---\nname: deploy\ndescription: Deploy\ncommand-template: claude /deploy {ticket}\n---\n",
+        );
+        assert_eq!(codes(&report), ["invalid-command-template"]);
+        assert_eq!(report.diagnostics[0].line, Some(4));
+    }
+
+    #[test]
+    fn command_template_may_use_declared_parameters() {
+        let report = lint(
+            "deploy",
+            "---\nname: deploy\ndescription: Deploy\ncommand-template: claude /deploy {ticket}\nparameters: [ticket]\n---\n",
+        );
+        assert!(report.diagnostics.is_empty(), "{:?}", report.diagnostics);
+    }
+
+    #[test]
+    fn parameters_named_after_placeholders_are_reported() {
+        let report = lint(
+            "deploy",
+            "---\nname: deploy\ndescription: Deploy\nparameters: [ticket, git_branch]\n---\n",
+        );
+        assert_eq!(codes(&report), ["invalid-parameter"]);
+        assert!(report.diagnostics[0].message.contains("git_branch"), "{}", report.diagnostics[0].message);
+        assert_eq!(report.diagnostics[0].line, Some(4));
+    }
+
+    #[test]
+    fn run_lint_main_exit_codes() {
+        assert_eq!(run_lint_main(["--bogus".to_string()].into_iter()), 2);
+
+        let env = TestEnv::new();
+        let skills = env.project().join(".claude").join("skills");
+        write_skill(&skills, "deploy", "---\nname: deploy\ndescription: Deploy\n---\n");
+        // 没有 SKILL.md 的资源目录不应被检查 / Asset folders without SKILL.md are not linted
+        fs::create_dir_all(skills.join("deploy").join("scripts")).unwrap();
+        fs::create_dir_all(skills.join("assets")).unwrap();
+
+        let args = |extra: &[&str]| {
+            ["--lint".to_string(), format!("--project-root={}", env.project().display())]
+                .into_iter()
+                .chain(extra.iter().map(|arg| arg.to_string()))
+                .collect::<Vec<_>>()
+                .into_iter()
+        };
+
+        let reports = lint_all_skills();
+        assert_eq!(reports.len(), 1);
+        assert!(reports[0].diagnostics.is_empty(), "{:?}", reports[0].diagnostics);
+        assert_eq!(run_lint_main(args(&[])), 0);
+
+        write_skill(&skills, "broken", "---\nname: other\ndescription: Broken\n---\n");
+        assert_eq!(run_lint_main(args(&["--json"])), 1);
+    }
+}
@@ -0,0 +1,376 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::context::ScanContext;
+use crate::claude_home::ClaudeHome;
+use crate::plugins::{plugin_skill_dirs, read_plugin_manifest, PathList};
+use crate::settings::LauncherSettings;
+use crate::skills::parse_skill_md;
+
+/// Plugin marketplace configuration
+/// Plugin marketplace 配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct MarketplaceConfig {
+    #[serde(default)]
+    pub(crate) source: Option<SourceInfo>,
+    #[serde(rename = "installLocation", alias = "install_location")]
+    pub(crate) install_location: String,
+    #[serde(rename = "lastUpdated", alias = "last_updated", default)]
+    pub(crate) last_updated: Option<String>,
+    #[serde(rename = "autoUpdate", alias = "auto_update", default)]
+    pub(crate) auto_update: Option<bool>,
+}
+
+/// Marketplace source, e.g. a GitHub repo or a local directory
+/// Marketplace 来源，例如 GitHub 仓库或本地目录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SourceInfo {
+    pub(crate) source: String,
+    #[serde(default)]
+    pub(crate) repo: Option<String>,
+    #[serde(default)]
+    pub(crate) url: Option<String>,
+    #[serde(default)]
+    pub(crate) path: Option<String>,
+}
+
+impl SourceInfo {
+    /// Human readable location of the source
+    /// 来源的可读位置
+    fn location(&self) -> Option<String> {
+        self.repo
+            .clone()
+            .or_else(|| self.url.clone())
+            .or_else(|| self.path.clone())
+    }
+}
+
+/// Marketplace manifest (.claude-plugin/marketplace.json)
+/// Marketplace 清单（.claude-plugin/marketplace.json）
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct MarketplaceManifest {
+    #[serde(default)]
+    pub(crate) name: Option<String>,
+    #[serde(default)]
+    pub(crate) owner: Option<MarketplaceOwner>,
+    #[serde(default)]
+    pub(crate) plugins: Vec<MarketplacePluginEntry>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct MarketplaceOwner {
+    #[serde(default)]
+    pub(crate) name: Option<String>,
+}
+
+/// A plugin offered by a marketplace
+/// Marketplace 提供的插件
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct MarketplacePluginEntry {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+    #[serde(default)]
+    pub(crate) version: Option<String>,
+    /// 相对路径字符串或远程来源对象 / Relative path string or a remote source object
+    #[serde(default)]
+    pub(crate) source: Option<serde_json::Value>,
+    /// 直接列出的 skill 目录（相对插件根目录）/ Skill directories listed directly, relative to the plugin root
+    #[serde(default)]
+    pub(crate) skills: Option<PathList>,
+}
+
+/// A skill offered by a marketplace plugin
+/// Marketplace 插件提供的 skill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketplaceSkillListing {
+    pub name: String,
+    pub description: String,
+    pub path: String,
+}
+
+/// A plugin offered by a marketplace, with install status
+/// Marketplace 提供的插件及其安装状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketplacePluginListing {
+    /// 插件完整 ID，格式为 "plugin-name@marketplace-id" / Full plugin id, "plugin-name@marketplace-id"
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub installed: bool,
+    #[serde(rename = "installedVersion", default, skip_serializing_if = "Option::is_none")]
+    pub installed_version: Option<String>,
+    pub skills: Vec<MarketplaceSkillListing>,
+}
+
+/// A locally cloned marketplace and everything it offers
+/// 本地克隆的 marketplace 及其提供的内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketplaceListing {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(rename = "installLocation")]
+    pub install_location: String,
+    #[serde(rename = "lastUpdated", default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<String>,
+    pub plugins: Vec<MarketplacePluginListing>,
+    /// 清单无法读取时的错误信息 / Error message when the manifest could not be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Built-in display names for well-known marketplaces
+/// 常见 marketplace 的内置显示名称
+fn builtin_marketplace_name(marketplace_id: &str) -> Option<&'static str> {
+    match marketplace_id {
+        "happy-claude-skills-gxj" => Some("Happy Claude"),
+        "superpowers-marketplace" => Some("Superpowers"),
+        "obsidian-skills" => Some("Obsidian"),
+        _ => None,
+    }
+}
+
+/// Resolve a marketplace display name from an already loaded manifest and settings
+/// 根据已读取的清单和设置解析 marketplace 显示名称
+/// 优先级：设置文件覆盖 > 内置名称 > marketplace 清单的 name/owner > ID 首字母大写
+/// Precedence: settings override > built-in name > manifest name/owner > title-cased id
+pub(crate) fn resolve_marketplace_name(
+    marketplace_id: &str,
+    manifest: Option<&MarketplaceManifest>,
+    settings: &LauncherSettings,
+) -> String {
+    if let Some(name) = settings.marketplace_names.get(marketplace_id) {
+        return name.clone();
+    }
+
+    if let Some(name) = builtin_marketplace_name(marketplace_id) {
+        return name.to_string();
+    }
+
+    manifest
+        .and_then(manifest_display_name)
+        .unwrap_or_else(|| title_case(marketplace_id))
+}
+
+/// Display name declared by a marketplace manifest
+/// marketplace 清单声明的显示名称
+/// name 通常是 kebab-case 标识，此时优先使用 owner.name，否则将其转换为首字母大写
+/// name is usually a slug; owner.name is preferred then, otherwise the slug is title-cased
+fn manifest_display_name(manifest: &MarketplaceManifest) -> Option<String> {
+    let name = manifest
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty());
+    let owner = manifest
+        .owner
+        .as_ref()
+        .and_then(|owner| owner.name.as_deref())
+        .map(str::trim)
+        .filter(|owner| !owner.is_empty());
+
+    match (name, owner) {
+        (Some(name), _) if !is_slug(name) => Some(name.to_string()),
+        (_, Some(owner)) => Some(owner.to_string()),
+        (Some(name), None) => Some(title_case(name)),
+        (None, None) => None,
+    }
+}
+
+/// Whether a name is a lowercase slug such as "my-marketplace"
+/// 名称是否为小写标识，例如 "my-marketplace"
+fn is_slug(name: &str) -> bool {
+    name.chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+/// Title-case an id, e.g. "my-marketplace" -> "My Marketplace"
+/// 将 ID 转换为首字母大写形式，例如 "my-marketplace" -> "My Marketplace"
+fn title_case(marketplace_id: &str) -> String {
+    // 从 ID 中提取友好名称 / Extract friendly name from ID
+    marketplace_id
+        .replace(['-', '_'], " ")
+        .split_whitespace()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Read marketplace configuration
+/// 读取 marketplace 配置
+pub(crate) fn read_marketplaces_config(claude_home: &ClaudeHome) -> HashMap<String, MarketplaceConfig> {
+    let plugins_dir = claude_home.plugins_dir();
+    let config_path = plugins_dir.join("known_marketplaces.json");
+
+    if !config_path.exists() {
+        return HashMap::new();
+    }
+
+    fs::read_to_string(&config_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Read a marketplace manifest from its local clone
+/// 从本地克隆读取 marketplace 清单
+pub(crate) fn read_marketplace_manifest(install_location: &Path) -> Result<MarketplaceManifest, String> {
+    let manifest_path = install_location.join(".claude-plugin").join("marketplace.json");
+
+    let content = fs::read_to_string(&manifest_path).map_err(|e| {
+        format!(
+            "无法读取 marketplace 清单 / Unable to read marketplace manifest {}: {}",
+            manifest_path.display(),
+            e
+        )
+    })?;
+
+    serde_json::from_str(&content).map_err(|e| {
+        format!(
+            "无法解析 marketplace 清单 / Unable to parse marketplace manifest {}: {}",
+            manifest_path.display(),
+            e
+        )
+    })
+}
+
+/// Collect skill directories: the directory itself if it holds SKILL.md, otherwise its children that do
+/// 收集 skill 目录：若目录本身包含 SKILL.md 则返回自身，否则返回包含 SKILL.md 的子目录
+fn collect_skill_dirs(dir: &Path) -> Vec<PathBuf> {
+    if dir.join("SKILL.md").is_file() {
+        return vec![dir.to_path_buf()];
+    }
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut skill_dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join("SKILL.md").is_file())
+        .collect();
+    skill_dirs.sort();
+    skill_dirs
+}
+
+/// Read name and description of a skill directory
+/// 读取 skill 目录的名称和描述
+fn read_skill_listing(skill_dir: &Path) -> Option<MarketplaceSkillListing> {
+    let content = fs::read_to_string(skill_dir.join("SKILL.md")).ok()?;
+    let (front_matter, _) = parse_skill_md(&content);
+    let dir_name = skill_dir.file_name()?.to_string_lossy().to_string();
+
+    Some(MarketplaceSkillListing {
+        name: front_matter.name.unwrap_or(dir_name),
+        description: front_matter.description.unwrap_or_default(),
+        path: skill_dir.to_string_lossy().to_string(),
+    })
+}
+
+/// List the skills a marketplace plugin entry offers in the local clone
+/// 列出 marketplace 插件条目在本地克隆中提供的 skills
+fn list_entry_skills(install_location: &Path, entry: &MarketplacePluginEntry) -> Vec<MarketplaceSkillListing> {
+    let mut skill_dirs = Vec::new();
+
+    // 相对路径来源才能在本地找到插件内容 / Only relative path sources exist in the local clone
+    let plugin_root = entry
+        .source
+        .as_ref()
+        .and_then(|source| source.as_str())
+        .map(|source| install_location.join(source.trim_start_matches("./")));
+
+    if let Some(paths) = entry.skills.as_ref() {
+        // skill 路径相对于插件根目录，缺省为 marketplace 根目录 / Skill paths are relative to the plugin root, or the marketplace root
+        let base = plugin_root.as_deref().unwrap_or(install_location);
+        for path in paths.to_vec() {
+            skill_dirs.extend(collect_skill_dirs(&base.join(path.trim_start_matches("./"))));
+        }
+    } else if let Some(plugin_root) = plugin_root.as_deref() {
+        let manifest = read_plugin_manifest(plugin_root).unwrap_or_default();
+        for dir in plugin_skill_dirs(plugin_root, &manifest) {
+            skill_dirs.extend(collect_skill_dirs(&dir));
+        }
+    }
+
+    skill_dirs.dedup();
+    skill_dirs
+        .iter()
+        .filter_map(|dir| read_skill_listing(dir))
+        .collect()
+}
+
+/// List every locally cloned marketplace and the plugins/skills it offers
+/// 列出所有本地克隆的 marketplace 及其提供的插件和 skills
+#[tauri::command]
+pub fn list_marketplaces() -> Vec<MarketplaceListing> {
+    let context = ScanContext::load();
+    let mut configs: Vec<(&String, &MarketplaceConfig)> = context.marketplaces.iter().collect();
+    configs.sort_by(|a, b| a.0.cmp(b.0));
+
+    configs
+        .into_iter()
+        .map(|(marketplace_id, config)| {
+            let install_location = PathBuf::from(&config.install_location);
+            let mut listing = MarketplaceListing {
+                id: marketplace_id.clone(),
+                name: context.marketplace_name(marketplace_id),
+                owner: None,
+                source: config.source.as_ref().and_then(SourceInfo::location),
+                install_location: config.install_location.clone(),
+                last_updated: config.last_updated.clone(),
+                plugins: Vec::new(),
+                error: None,
+            };
+
+            let manifest = match read_marketplace_manifest(&install_location) {
+                Ok(manifest) => manifest,
+                Err(e) => {
+                    listing.error = Some(e);
+                    return listing;
+                }
+            };
+
+            listing.owner = manifest.owner.as_ref().and_then(|owner| owner.name.clone());
+            listing.plugins = manifest
+                .plugins
+                .iter()
+                .map(|entry| {
+                    let plugin_id = format!("{}@{}", entry.name, marketplace_id);
+                    let installed_version = context
+                        .installed_plugins
+                        .plugins
+                        .get(&plugin_id)
+                        .and_then(|versions| versions.first())
+                        .map(|version| version.version.clone());
+
+                    MarketplacePluginListing {
+                        id: plugin_id,
+                        name: entry.name.clone(),
+                        description: entry.description.clone().unwrap_or_default(),
+                        version: entry.version.clone(),
+                        installed: installed_version.is_some(),
+                        installed_version,
+                        skills: list_entry_skills(&install_location, entry),
+                    }
+                })
+                .collect();
+
+            listing
+        })
+        .collect()
+}
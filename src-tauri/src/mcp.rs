@@ -0,0 +1,179 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::claude_home::ClaudeHome;
+use crate::skills::{get_project_root, ScanError};
+
+/// MCP server entry as written in .mcp.json or Claude settings
+/// .mcp.json 或 Claude 设置中的 MCP server 配置项
+#[derive(Debug, Clone, Default, Deserialize)]
+struct McpServerConfig {
+    #[serde(rename = "type", default)]
+    transport: Option<String>,
+    #[serde(default)]
+    command: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    url: Option<String>,
+}
+
+/// An MCP server available in the current project
+/// 当前项目可用的 MCP server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpServer {
+    pub name: String,
+    /// "stdio"、"sse" 或 "http" / "stdio", "sse" or "http"
+    pub transport: String,
+    /// stdio server 的完整命令行 / Full command line of a stdio server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// 远程 server 的地址 / URL of a remote server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// "local"、"project" 或 "user" / "local", "project" or "user"
+    pub scope: String,
+    /// 声明该 server 的配置文件 / Config file declaring the server
+    pub source: String,
+}
+
+/// MCP servers and the config files that could not be read
+/// MCP server 列表以及无法读取的配置文件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct McpServerList {
+    pub servers: Vec<McpServer>,
+    pub errors: Vec<ScanError>,
+}
+
+/// Read a JSON config file, recording an error when it exists but is invalid
+/// 读取 JSON 配置文件，文件存在但无效时记录错误
+fn read_json(path: &Path, errors: &mut Vec<ScanError>) -> Option<serde_json::Value> {
+    let content = fs::read_to_string(path).ok()?;
+
+    match serde_json::from_str(&content) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            errors.push(ScanError {
+                path: path.to_string_lossy().to_string(),
+                message: format!("配置文件不是有效的 JSON / Invalid JSON config: {}", e),
+            });
+            None
+        }
+    }
+}
+
+/// Keys a project may be stored under in .claude.json
+/// 项目在 .claude.json 中可能使用的键
+/// Windows 上 Claude Code 以正斜杠记录路径，因此同时尝试两种分隔符
+/// Claude Code records Windows paths with forward slashes, so both separators are tried
+fn project_keys(project_root: &Path) -> Vec<String> {
+    let key = project_root.to_string_lossy().to_string();
+    let forward = key.replace('\\', "/");
+    if forward == key {
+        vec![key]
+    } else {
+        vec![forward, key]
+    }
+}
+
+impl McpServerList {
+    /// Append the servers of an mcpServers object, skipping names already listed by a higher-precedence scope
+    /// 追加 mcpServers 对象中的 server，跳过已由更高优先级范围列出的同名 server
+    fn push_servers(
+        &mut self,
+        seen: &mut HashSet<String>,
+        servers: Option<&serde_json::Value>,
+        scope: &str,
+        source: &Path,
+    ) {
+        let Some(servers) = servers else {
+            return;
+        };
+
+        let servers: HashMap<String, McpServerConfig> = match serde_json::from_value(servers.clone()) {
+            Ok(servers) => servers,
+            Err(e) => {
+                self.errors.push(ScanError {
+                    path: source.to_string_lossy().to_string(),
+                    message: format!("mcpServers 格式无效 / Invalid mcpServers: {}", e),
+                });
+                return;
+            }
+        };
+
+        let mut names: Vec<&String> = servers.keys().collect();
+        names.sort();
+
+        for name in names {
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+
+            let config = &servers[name];
+            // 未声明 type 时按字段推断 / Infer the transport when no type is declared
+            let transport = config.transport.clone().unwrap_or_else(|| {
+                if config.command.is_some() { "stdio" } else { "http" }.to_string()
+            });
+            let command = config
+                .command
+                .as_ref()
+                .map(|command| std::iter::once(command).chain(&config.args).cloned().collect::<Vec<_>>().join(" "));
+
+            self.servers.push(McpServer {
+                name: name.clone(),
+                transport,
+                command,
+                url: config.url.clone(),
+                scope: scope.to_string(),
+                source: source.to_string_lossy().to_string(),
+            });
+        }
+    }
+}
+
+/// List MCP servers available in the current project
+/// 列出当前项目可用的 MCP servers
+/// 同名 server 按 Claude Code 的优先级取第一个：local > project > user
+/// Same-name servers resolve the way Claude Code does: local > project > user
+#[tauri::command]
+pub fn list_mcp_servers() -> McpServerList {
+    let claude_home = ClaudeHome::resolve();
+    let project_root = get_project_root();
+
+    let mut list = McpServerList::default();
+    let mut seen = HashSet::new();
+
+    let global_path = claude_home.global_config_path();
+    let global = read_json(&global_path, &mut list.errors);
+
+    if let Some(project_root) = project_root.as_deref() {
+        // 1. .claude.json 中按项目路径记录的 local 范围 / Local scope stored per project path in .claude.json
+        let local = global
+            .as_ref()
+            .and_then(|global| global.get("projects"))
+            .and_then(|projects| project_keys(project_root).iter().find_map(|key| projects.get(key)))
+            .and_then(|project| project.get("mcpServers"));
+        list.push_servers(&mut seen, local, "local", &global_path);
+
+        let local_settings = project_root.join(".claude").join("settings.local.json");
+        let value = read_json(&local_settings, &mut list.errors);
+        list.push_servers(&mut seen, value.as_ref().and_then(|v| v.get("mcpServers")), "local", &local_settings);
+
+        // 2. 项目根目录的 .mcp.json 和项目设置 / .mcp.json at the project root and project settings
+        for path in [project_root.join(".mcp.json"), project_root.join(".claude").join("settings.json")] {
+            let value = read_json(&path, &mut list.errors);
+            list.push_servers(&mut seen, value.as_ref().and_then(|v| v.get("mcpServers")), "project", &path);
+        }
+    }
+
+    // 3. 用户范围 / User scope
+    list.push_servers(&mut seen, global.as_ref().and_then(|v| v.get("mcpServers")), "user", &global_path);
+
+    let user_settings = claude_home.settings_path();
+    let value = read_json(&user_settings, &mut list.errors);
+    list.push_servers(&mut seen, value.as_ref().and_then(|v| v.get("mcpServers")), "user", &user_settings);
+
+    list
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::context::ScanContext;
+use crate::plugins::PluginRef;
+
+/// Marketplace ids published by Anthropic
+/// Anthropic 发布的 marketplace ID
+const OFFICIAL_MARKETPLACE_IDS: &[&str] = &["anthropic-agent-skills", "claude-plugins-official"];
+
+/// GitHub owner of Anthropic's marketplace repositories
+/// Anthropic marketplace 仓库所属的 GitHub 账号
+const OFFICIAL_SOURCE_OWNER: &str = "anthropics/";
+
+/// Which rule marked a skill as official
+/// 判定为官方 skill 所依据的规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OfficialRule {
+    /// 来自 Anthropic 的 marketplace ID / Installed from an Anthropic marketplace id
+    Marketplace,
+    /// marketplace 来源为 Anthropic 的仓库 / Marketplace source is an Anthropic repository
+    Source,
+    /// SKILL.md 内容哈希在本地白名单中 / SKILL.md content hash is on the local allow-list
+    Hash,
+}
+
+/// Official status of a skill and why
+/// skill 的官方状态及原因
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OfficialStatus {
+    pub rule: OfficialRule,
+    /// 说明匹配的规则 / Explains which rule matched
+    pub label: String,
+    /// 内容哈希是否在白名单中 / Whether the content hash is on the allow-list
+    pub verified: bool,
+}
+
+/// SHA-256 of SKILL.md content, with CRLF normalised to LF
+/// SKILL.md 内容的 SHA-256，CRLF 统一为 LF
+pub fn skill_content_hash(content: &str) -> String {
+    let normalized = content.replace("\r\n", "\n");
+    Sha256::digest(normalized.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Decide whether a skill is official from where it was installed
+/// 根据安装来源判断 skill 是否为官方 skill
+/// plugin: 根据文件位置确定的所属插件 / Owning plugin determined from the file location
+/// content_hash: SKILL.md 内容的哈希，见 skill_content_hash / SKILL.md hash from skill_content_hash
+pub(crate) fn detect_official(
+    context: &ScanContext,
+    plugin: Option<&PluginRef>,
+    content_hash: &str,
+) -> Option<OfficialStatus> {
+    let settings = &context.settings;
+
+    let verified = settings
+        .official_skill_hashes
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(content_hash));
+
+    if let Some(plugin) = plugin {
+        let marketplace_id = plugin.marketplace_id.as_str();
+
+        // 1. 来自 Anthropic 的 marketplace / From an Anthropic marketplace id
+        let is_official_marketplace = OFFICIAL_MARKETPLACE_IDS.contains(&marketplace_id)
+            || settings
+                .official_marketplaces
+                .iter()
+                .any(|id| id == marketplace_id);
+        if is_official_marketplace {
+            return Some(OfficialStatus {
+                rule: OfficialRule::Marketplace,
+                label: format!(
+                    "从 marketplace \"{}\" 安装 / Installed from marketplace \"{}\"",
+                    marketplace_id, marketplace_id
+                ),
+                verified,
+            });
+        }
+
+        // 2. marketplace 来源为 Anthropic 的仓库 / Marketplace source is an Anthropic repository
+        let source_repo = context
+            .marketplaces
+            .get(marketplace_id)
+            .and_then(|config| config.source.as_ref())
+            .and_then(|source| source.repo.clone());
+        if let Some(repo) = source_repo.filter(|repo| repo.starts_with(OFFICIAL_SOURCE_OWNER)) {
+            return Some(OfficialStatus {
+                rule: OfficialRule::Source,
+                label: format!(
+                    "marketplace \"{}\" 来源于 {} / Marketplace \"{}\" is sourced from {}",
+                    marketplace_id, repo, marketplace_id, repo
+                ),
+                verified,
+            });
+        }
+    }
+
+    // 3. 本地白名单中的内容哈希 / Content hash on the local allow-list
+    if verified {
+        return Some(OfficialStatus {
+            rule: OfficialRule::Hash,
+            label: "SKILL.md 内容哈希在官方白名单中 / SKILL.md content hash is on the official allow-list".to_string(),
+            verified,
+        });
+    }
+
+    None
+}
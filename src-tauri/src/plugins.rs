@@ -0,0 +1,254 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::claude_home::ClaudeHome;
+
+/// Installed plugins configuration
+/// 已安装的 plugins 配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct InstalledPluginsConfig {
+    pub(crate) plugins: std::collections::HashMap<String, Vec<PluginVersion>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct PluginVersion {
+    pub(crate) scope: String,
+    #[serde(rename = "installPath")]
+    pub(crate) install_path: String,
+    #[serde(default)]
+    pub(crate) version: String,
+    #[serde(rename = "installedAt", default)]
+    pub(crate) installed_at: String,
+    #[serde(rename = "lastUpdated", default)]
+    pub(crate) last_updated: String,
+    #[serde(rename = "gitCommitSha", default)]
+    pub(crate) git_commit_sha: String,
+    /// 项目级安装对应的项目路径 / Project path for project-scoped installs
+    #[serde(rename = "projectPath", default)]
+    pub(crate) project_path: Option<String>,
+}
+
+/// Plugin manifest (.claude-plugin/plugin.json)
+/// 插件清单（.claude-plugin/plugin.json）
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct PluginManifest {
+    #[serde(default)]
+    pub(crate) name: Option<String>,
+    #[serde(default)]
+    pub(crate) version: Option<String>,
+    /// 额外的 skills 目录（相对插件根目录）/ Extra skills directories relative to the plugin root
+    #[serde(default)]
+    pub(crate) skills: Option<PathList>,
+}
+
+/// A single path or a list of paths
+/// 单个路径或路径列表
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum PathList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl PathList {
+    pub(crate) fn to_vec(&self) -> Vec<String> {
+        match self {
+            PathList::One(path) => vec![path.clone()],
+            PathList::Many(paths) => paths.clone(),
+        }
+    }
+}
+
+/// Plugin a skill was loaded from
+/// skill 所属的插件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginRef {
+    /// 插件完整 ID，格式为 "plugin-name@marketplace-id" / Full plugin id, "plugin-name@marketplace-id"
+    pub id: String,
+    /// 用于命名空间的插件名 / Plugin name used as the skill namespace
+    pub name: String,
+    pub version: String,
+    #[serde(rename = "marketplaceId")]
+    pub marketplace_id: String,
+    /// 安装范围，例如 "user" 或 "project" / Install scope, e.g. "user" or "project"
+    #[serde(default)]
+    pub scope: String,
+    #[serde(rename = "gitCommitSha", default, skip_serializing_if = "Option::is_none")]
+    pub git_commit_sha: Option<String>,
+    #[serde(rename = "installedAt", default, skip_serializing_if = "Option::is_none")]
+    pub installed_at: Option<String>,
+    #[serde(rename = "lastUpdated", default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<String>,
+}
+
+/// How a skill's origin was determined
+/// skill 来源的判定方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProvenanceSource {
+    /// skill 位于插件安装目录内 / Skill lives inside a plugin's installPath
+    Plugin,
+    /// 根据名称别名推测，可能不准确 / Inferred from name aliases, may be wrong
+    Guessed,
+    /// 本地 skill，不属于任何插件 / Local skill, not from any plugin
+    Local,
+}
+
+/// Where a skill came from
+/// skill 的来源
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillProvenance {
+    pub source: ProvenanceSource,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin: Option<PluginRef>,
+}
+
+impl SkillProvenance {
+    pub(crate) fn local() -> Self {
+        SkillProvenance {
+            source: ProvenanceSource::Local,
+            plugin: None,
+        }
+    }
+
+    pub(crate) fn plugin(plugin: PluginRef) -> Self {
+        SkillProvenance {
+            source: ProvenanceSource::Plugin,
+            plugin: Some(plugin),
+        }
+    }
+
+    pub(crate) fn guessed(plugin: Option<PluginRef>) -> Self {
+        SkillProvenance {
+            source: ProvenanceSource::Guessed,
+            plugin,
+        }
+    }
+}
+
+/// Read installed plugins configuration
+/// 读取已安装的 plugins 配置
+pub(crate) fn read_installed_plugins(claude_home: &ClaudeHome) -> InstalledPluginsConfig {
+    let plugins_dir = claude_home.plugins_dir();
+    let config_path = plugins_dir.join("installed_plugins.json");
+
+    if !config_path.exists() {
+        return InstalledPluginsConfig {
+            plugins: std::collections::HashMap::new(),
+        };
+    }
+
+    fs::read_to_string(&config_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_else(|| InstalledPluginsConfig {
+            plugins: std::collections::HashMap::new(),
+        })
+}
+
+/// Read a plugin's manifest from its install path
+/// 从插件安装目录读取插件清单
+pub(crate) fn read_plugin_manifest(install_path: &Path) -> Option<PluginManifest> {
+    let manifest_path = install_path.join(".claude-plugin").join("plugin.json");
+
+    fs::read_to_string(&manifest_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// Canonicalize a path, falling back to the path itself
+/// 规范化路径，失败时返回原路径
+pub(crate) fn canonical_path(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Build a plugin reference from an install record and its manifest
+/// 根据安装记录和插件清单构建插件引用
+pub(crate) fn build_plugin_ref(plugin_id: &str, version: &PluginVersion, manifest: &PluginManifest) -> PluginRef {
+    let (plugin_name, marketplace_id) = plugin_id.split_once('@').unwrap_or((plugin_id, ""));
+
+    PluginRef {
+        id: plugin_id.to_string(),
+        name: manifest.name.clone().unwrap_or_else(|| plugin_name.to_string()),
+        version: if version.version.is_empty() {
+            manifest.version.clone().unwrap_or_default()
+        } else {
+            version.version.clone()
+        },
+        marketplace_id: marketplace_id.to_string(),
+        scope: version.scope.clone(),
+        git_commit_sha: non_empty(&version.git_commit_sha),
+        installed_at: non_empty(&version.installed_at),
+        last_updated: non_empty(&version.last_updated),
+    }
+}
+
+/// Convert an empty string to None
+/// 空字符串转换为 None
+fn non_empty(value: &str) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// Check whether a plugin install applies to the current project
+/// 检查插件安装记录是否适用于当前项目
+fn applies_to_project(version: &PluginVersion, project_root: Option<&Path>) -> bool {
+    // 用户级安装对所有项目生效 / User-scoped installs apply everywhere
+    let Some(project_path) = version.project_path.as_deref() else {
+        return true;
+    };
+    let Some(project_root) = project_root else {
+        return false;
+    };
+
+    canonical_path(Path::new(project_path)) == canonical_path(project_root)
+}
+
+/// Get the skills directories of a plugin root
+/// 获取插件根目录下的 skills 目录
+/// 包含默认的 skills/ 目录以及插件清单中声明的目录 / The default skills/ folder plus any declared in the manifest
+pub(crate) fn plugin_skill_dirs(plugin_root: &Path, manifest: &PluginManifest) -> Vec<PathBuf> {
+    let mut skill_dirs = vec![plugin_root.join("skills")];
+    for extra in manifest.skills.iter().flat_map(PathList::to_vec) {
+        let extra_dir = plugin_root.join(extra.trim_start_matches("./"));
+        if !skill_dirs.contains(&extra_dir) {
+            skill_dirs.push(extra_dir);
+        }
+    }
+    skill_dirs
+}
+
+/// Get skills directories of every installed plugin
+/// 获取所有已安装插件的 skills 目录
+pub(crate) fn get_plugin_skill_directories(
+    installed_plugins: &InstalledPluginsConfig,
+    project_root: Option<&Path>,
+) -> Vec<(PathBuf, PluginRef)> {
+    let mut plugin_ids: Vec<&String> = installed_plugins.plugins.keys().collect();
+    plugin_ids.sort();
+
+    let mut dirs = Vec::new();
+
+    for plugin_id in plugin_ids {
+        for version in installed_plugins.plugins[plugin_id].iter() {
+            if !applies_to_project(version, project_root) {
+                continue;
+            }
+
+            let install_path = PathBuf::from(&version.install_path);
+            let manifest = read_plugin_manifest(&install_path).unwrap_or_default();
+
+            let plugin = build_plugin_ref(plugin_id, version, &manifest);
+
+            for skill_dir in plugin_skill_dirs(&install_path, &manifest) {
+                dirs.push((skill_dir, plugin.clone()));
+            }
+        }
+    }
+
+    dirs
+}
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::agent_profiles::{AgentProfile, CLAUDE_PROFILE};
+
+/// Tauri app identifier, used as the settings folder name
+/// Tauri 应用标识符，用作设置目录名
+pub(crate) const APP_IDENTIFIER: &str = "com.skillLauncher.app";
+
+/// Launcher settings file (settings.json)
+/// 启动器设置文件（settings.json）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LauncherSettings {
+    /// marketplace ID 到显示名称的覆盖 / Marketplace id to display name overrides
+    #[serde(rename = "marketplaceNames", default)]
+    pub marketplace_names: HashMap<String, String>,
+    /// 额外视为官方的 marketplace ID / Extra marketplace ids treated as official
+    #[serde(rename = "officialMarketplaces", default)]
+    pub official_marketplaces: Vec<String>,
+    /// 官方 SKILL.md 的 SHA-256 白名单 / SHA-256 allow-list of official SKILL.md files
+    #[serde(rename = "officialSkillHashes", default)]
+    pub official_skill_hashes: Vec<String>,
+    /// skills 目录下搜索 skill 的最大层数 / Max folder levels searched below a skills directory
+    #[serde(rename = "skillSearchDepth", default, skip_serializing_if = "Option::is_none")]
+    pub skill_search_depth: Option<usize>,
+    /// 额外的 skills 目录 / Additional skills directories
+    #[serde(rename = "extraSkillPaths", default)]
+    pub extra_skill_paths: Vec<ExtraSkillPath>,
+    /// 覆盖 Claude Code 配置目录，优先于 CLAUDE_CONFIG_DIR / Claude Code config dir override, wins over CLAUDE_CONFIG_DIR
+    #[serde(rename = "claudeConfigDir", default, skip_serializing_if = "Option::is_none")]
+    pub claude_config_dir: Option<String>,
+    /// 组织托管配置目录，覆盖系统默认位置 / Organisation-managed config dir, overrides the system default
+    #[serde(rename = "managedConfigDir", default, skip_serializing_if = "Option::is_none")]
+    pub managed_config_dir: Option<String>,
+    /// 自定义代理配置，可替换内置的 claude 和 codex / User-defined agent profiles, may replace the built-in claude and codex
+    #[serde(rename = "agentProfiles", default)]
+    pub agent_profiles: Vec<AgentProfile>,
+}
+
+/// An additional skills directory configured by the user
+/// 用户配置的额外 skills 目录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtraSkillPath {
+    /// 目录路径，支持以 ~ 开头 / Directory path, may start with ~
+    pub path: String,
+    /// 该目录下 skills 的范围标签，例如 "team" / Scope label for its skills, e.g. "team"
+    #[serde(default = "default_extra_scope")]
+    pub scope: String,
+    /// 优先级，数值越大越先搜索 / Higher values are searched first and win name collisions
+    #[serde(default)]
+    pub priority: i32,
+    /// 该目录所属的代理配置 ID / Agent profile id the directory belongs to
+    #[serde(default = "default_extra_agent")]
+    pub agent: String,
+}
+
+fn default_extra_scope() -> String {
+    "user".to_string()
+}
+
+fn default_extra_agent() -> String {
+    CLAUDE_PROFILE.to_string()
+}
+
+/// Get settings file path
+/// 获取设置文件路径
+/// 可通过 SKILL_LAUNCHER_SETTINGS 环境变量覆盖 / Can be overridden with SKILL_LAUNCHER_SETTINGS
+pub fn get_settings_path() -> PathBuf {
+    if let Ok(path) = std::env::var("SKILL_LAUNCHER_SETTINGS") {
+        return PathBuf::from(path);
+    }
+
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_IDENTIFIER)
+        .join("settings.json")
+}
+
+/// Get extra skills directories from settings and SKILL_LAUNCHER_SKILL_PATHS
+/// 从设置和 SKILL_LAUNCHER_SKILL_PATHS 环境变量获取额外的 skills 目录
+/// 环境变量按系统路径分隔符拆分（Windows 为 ";"）/ The env var is split on the OS path separator (";" on Windows)
+pub fn get_extra_skill_paths(settings: &LauncherSettings) -> Vec<ExtraSkillPath> {
+    let mut paths = settings.extra_skill_paths.clone();
+
+    if let Some(value) = std::env::var_os("SKILL_LAUNCHER_SKILL_PATHS") {
+        paths.extend(
+            std::env::split_paths(&value)
+                .filter(|path| !path.as_os_str().is_empty())
+                .map(|path| ExtraSkillPath {
+                    path: path.to_string_lossy().to_string(),
+                    scope: default_extra_scope(),
+                    priority: 0,
+                    agent: default_extra_agent(),
+                }),
+        );
+    }
+
+    paths
+}
+
+/// Load launcher settings, falling back to defaults when missing or invalid
+/// 读取启动器设置，文件缺失或无效时使用默认值
+pub fn load_settings() -> LauncherSettings {
+    let settings_path = get_settings_path();
+
+    let Ok(content) = fs::read_to_string(&settings_path) else {
+        return LauncherSettings::default();
+    };
+
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        eprintln!("⚠️ 设置文件无效 / Invalid settings file {}: {}", settings_path.display(), e);
+        LauncherSettings::default()
+    })
+}
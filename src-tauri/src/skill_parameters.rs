@@ -0,0 +1,487 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::command_template::{context_values, render_template, TemplateInput, TEMPLATE_PLACEHOLDERS};
+use crate::skills::{deserialize_scalar, deserialize_string_list, parse_skill_content, read_skill_file, ParsedSkill};
+
+/// Value type of a skill parameter
+/// skill 参数的值类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParameterType {
+    #[default]
+    String,
+    Number,
+    Integer,
+    Boolean,
+    /// 文件或目录路径 / File or directory path
+    Path,
+}
+
+/// A named parameter declared in SKILL.md front matter
+/// SKILL.md Front Matter 中声明的命名参数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillParameter {
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub kind: ParameterType,
+    #[serde(default)]
+    pub required: bool,
+    /// 可选值，非空时输入必须是其中之一 / Allowed choices, input must be one of them when not empty
+    #[serde(
+        rename = "enum",
+        default,
+        deserialize_with = "deserialize_string_list",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub choices: Vec<String>,
+    #[serde(default, deserialize_with = "deserialize_scalar", skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_scalar", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Accept parameters as a list of names or objects with a name, or as a mapping from name to spec
+/// 参数可写成参数名或带 name 的对象列表，也可写成参数名到定义的映射
+/// 映射形式保留书写顺序；无效的条目和与内置占位符同名的参数被跳过并随结果返回其错误，由 lint 报告
+/// The mapping form keeps the declared order; invalid entries and parameters named after a built-in placeholder
+/// are skipped and their errors returned for lint to report
+pub(crate) fn parse_parameters(value: serde_yaml::Value) -> (Vec<SkillParameter>, Vec<String>) {
+    let entries: Vec<serde_yaml::Value> = match value {
+        serde_yaml::Value::Null => return (Vec::new(), Vec::new()),
+        serde_yaml::Value::Sequence(items) => items
+            .into_iter()
+            .map(|item| match item {
+                // "[ticket, env]" 简写 / "[ticket, env]" shorthand
+                serde_yaml::Value::String(name) => {
+                    let mut spec = serde_yaml::Mapping::new();
+                    spec.insert("name".into(), name.into());
+                    serde_yaml::Value::Mapping(spec)
+                }
+                item => item,
+            })
+            .collect(),
+        serde_yaml::Value::Mapping(mapping) => mapping
+            .into_iter()
+            .map(|(name, spec)| {
+                let mut spec = match spec {
+                    serde_yaml::Value::Mapping(spec) => spec,
+                    // "ticket: string" 简写 / "ticket: string" shorthand
+                    serde_yaml::Value::String(kind) => {
+                        let mut spec = serde_yaml::Mapping::new();
+                        spec.insert("type".into(), kind.into());
+                        spec
+                    }
+                    _ => serde_yaml::Mapping::new(),
+                };
+                spec.insert("name".into(), name);
+                serde_yaml::Value::Mapping(spec)
+            })
+            .collect(),
+        _ => {
+            return (
+                Vec::new(),
+                vec!["parameters 必须是列表或映射 / parameters must be a list or a mapping".to_string()],
+            )
+        }
+    };
+
+    let mut parameters = Vec::new();
+    let mut errors = Vec::new();
+    for (position, entry) in entries.into_iter().enumerate() {
+        match serde_yaml::from_value::<SkillParameter>(entry) {
+            // 同名参数会覆盖上下文的值 / A parameter with the same name would overwrite the context value
+            Ok(parameter) if TEMPLATE_PLACEHOLDERS.contains(&parameter.name.as_str()) => errors.push(format!(
+                "参数名 {} 是内置占位符，请换一个名称 / Parameter name {} is reserved for a built-in placeholder",
+                parameter.name, parameter.name
+            )),
+            Ok(parameter) => parameters.push(parameter),
+            Err(e) => errors.push(format!(
+                "第 {} 个参数无效 / Parameter #{} is invalid: {}",
+                position + 1,
+                position + 1,
+                e
+            )),
+        }
+    }
+
+    (parameters, errors)
+}
+
+/// Deserialize parameters, skipping invalid entries instead of failing the whole front matter
+/// 反序列化参数，跳过无效的条目而不是让整个 Front Matter 解析失败
+pub(crate) fn deserialize_parameters<'de, D>(deserializer: D) -> Result<Vec<SkillParameter>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_yaml::Value>::deserialize(deserializer)?;
+    Ok(parse_parameters(value.unwrap_or_default()).0)
+}
+
+/// Check one value against its parameter, returning the normalised value
+/// 按参数定义校验单个值，返回规范化后的值
+fn validate_value(parameter: &SkillParameter, value: &str) -> Result<String, String> {
+    let value = value.trim();
+
+    if !parameter.choices.is_empty() && !parameter.choices.iter().any(|choice| choice == value) {
+        return Err(format!(
+            "参数 {} 必须是 {} 之一 / Parameter {} must be one of: {}",
+            parameter.name,
+            parameter.choices.join(", "),
+            parameter.name,
+            parameter.choices.join(", ")
+        ));
+    }
+
+    let valid = match parameter.kind {
+        ParameterType::String | ParameterType::Path => !value.is_empty(),
+        ParameterType::Number => value.parse::<f64>().is_ok_and(f64::is_finite),
+        ParameterType::Integer => value.parse::<i64>().is_ok(),
+        ParameterType::Boolean => {
+            return match value.to_ascii_lowercase().as_str() {
+                "true" | "yes" | "1" => Ok("true".to_string()),
+                "false" | "no" | "0" => Ok("false".to_string()),
+                _ => Err(format!(
+                    "参数 {} 必须是 true 或 false / Parameter {} must be true or false",
+                    parameter.name, parameter.name
+                )),
+            };
+        }
+    };
+
+    if valid {
+        Ok(value.to_string())
+    } else {
+        let kind = serde_json::to_value(parameter.kind)
+            .ok()
+            .and_then(|kind| kind.as_str().map(str::to_string))
+            .unwrap_or_default();
+        Err(format!(
+            "参数 {} 的值 \"{}\" 不是有效的 {} / Parameter {} value \"{}\" is not a valid {}",
+            parameter.name, value, kind, parameter.name, value, kind
+        ))
+    }
+}
+
+/// Validate input against the declared parameters, applying defaults
+/// 按声明的参数校验输入并应用默认值
+/// 返回按声明顺序排列的值，所有错误一并返回 / Returns values in declared order; every problem is reported at once
+pub(crate) fn validate_parameters(
+    parameters: &[SkillParameter],
+    values: &HashMap<String, String>,
+) -> Result<Vec<Option<String>>, String> {
+    let mut errors = Vec::new();
+
+    let mut unknown: Vec<&String> = values
+        .keys()
+        .filter(|name| !parameters.iter().any(|parameter| &parameter.name == *name))
+        .collect();
+    unknown.sort();
+    for name in unknown {
+        errors.push(format!("未知参数 {} / Unknown parameter {}", name, name));
+    }
+
+    let resolved = parameters
+        .iter()
+        .map(|parameter| {
+            let value = values
+                .get(&parameter.name)
+                .filter(|value| !value.trim().is_empty())
+                .or(parameter.default.as_ref());
+
+            match value {
+                Some(value) => validate_value(parameter, value).map_err(|e| errors.push(e)).ok(),
+                None => {
+                    if parameter.required {
+                        errors.push(format!(
+                            "缺少必填参数 {} / Missing required parameter {}",
+                            parameter.name, parameter.name
+                        ));
+                    }
+                    None
+                }
+            }
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(resolved)
+    } else {
+        Err(errors.join("\n"))
+    }
+}
+
+/// Quote an argument that contains whitespace or quotes
+/// 为包含空白或引号的参数加引号
+fn quote_argument(value: &str) -> String {
+    if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '"') {
+        format!("\"{}\"", value.replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Read a skill's declaration from disk: a skill folder, or a command or agent markdown file
+/// 从磁盘读取 skill 的声明：skill 目录，或命令、代理的 markdown 文件
+fn read_declaration(path: &Path) -> Result<ParsedSkill, String> {
+    let parsed = if path.is_file() {
+        let fallback_name = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+        fs::read_to_string(path).ok().map(|content| parse_skill_content(path, fallback_name, &content))
+    } else {
+        read_skill_file(path)
+    };
+
+    parsed.ok_or_else(|| {
+        format!(
+            "无法读取 skill 声明：{} / Unable to read the skill declaration: {}",
+            path.display(),
+            path.display()
+        )
+    })
+}
+
+/// Validate parameter values and build the final command text
+/// 校验参数值并生成最终的命令文本
+/// 有命令模板时参数作为占位符代入，否则按声明顺序作为位置参数追加在调用文本后
+/// With a command template the parameters fill its placeholders, otherwise they follow the invocation as positional arguments
+pub(crate) fn build_command(
+    invocation: String,
+    command_template: Option<String>,
+    parameters: &[SkillParameter],
+    values: HashMap<String, String>,
+    input: TemplateInput,
+) -> Result<String, String> {
+    let resolved = validate_parameters(parameters, &values)?;
+
+    if let Some(template) = command_template.filter(|template| !template.is_empty()) {
+        let names: Vec<&str> = parameters.iter().map(|parameter| parameter.name.as_str()).collect();
+        let mut template_values: HashMap<&str, String> = context_values(input);
+        // 未填写且没有默认值的可选参数代入空字符串 / Optional parameters left empty without a default render as ""
+        for (name, value) in names.iter().zip(resolved) {
+            template_values.insert(*name, value.unwrap_or_default());
+        }
+        return render_template(&template, &template_values, &names);
+    }
+
+    // 跳过末尾缺省的可选参数，中间缺省的以 "" 占位 / Drop trailing omitted optionals, keep positions with "" in between
+    let last = resolved.iter().rposition(Option::is_some).map_or(0, |index| index + 1);
+    let arguments: Vec<String> = resolved[..last]
+        .iter()
+        .map(|value| quote_argument(value.as_deref().unwrap_or_default()))
+        .collect();
+
+    if arguments.is_empty() {
+        Ok(invocation)
+    } else {
+        Ok(format!("{} {}", invocation, arguments.join(" ")))
+    }
+}
+
+/// Build the command for the skill at path, validating against the parameters and template in its file
+/// 为 path 处的 skill 生成命令，按其文件中声明的参数和模板校验
+/// 声明从磁盘重新读取，不信任 webview 传入的定义 / The declaration is re-read from disk rather than trusted from the webview
+#[tauri::command]
+pub fn build_skill_command(
+    invocation: String,
+    path: String,
+    values: HashMap<String, String>,
+    input: TemplateInput,
+) -> Result<String, String> {
+    let declaration = read_declaration(Path::new(&path))?;
+    build_command(invocation, declaration.command_template, &declaration.parameters, values, input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameter(name: &str) -> SkillParameter {
+        SkillParameter {
+            name: name.to_string(),
+            kind: ParameterType::String,
+            required: false,
+            choices: Vec::new(),
+            default: None,
+            description: None,
+        }
+    }
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    fn parse(yaml: &str) -> (Vec<SkillParameter>, Vec<String>) {
+        parse_parameters(serde_yaml::from_str(yaml).unwrap())
+    }
+
+    #[test]
+    fn bare_names_become_string_parameters() {
+        let (parameters, errors) = parse("[ticket, env]");
+        assert!(errors.is_empty());
+        assert_eq!(parameters, vec![parameter("ticket"), parameter("env")]);
+    }
+
+    #[test]
+    fn mapping_form_keeps_order_and_type_shorthand() {
+        let (parameters, errors) = parse("ticket: {required: true}\nreplicas: integer");
+        assert!(errors.is_empty());
+        assert_eq!(parameters[0].name, "ticket");
+        assert!(parameters[0].required);
+        assert_eq!(parameters[1].kind, ParameterType::Integer);
+    }
+
+    #[test]
+    fn invalid_entries_are_skipped_and_reported() {
+        let (parameters, errors) = parse("[ticket, {type: number}, {name: env, type: bogus}, {name: n, type: number}]");
+        assert_eq!(parameters.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(), ["ticket", "n"]);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("#2"), "{}", errors[0]);
+    }
+
+    #[test]
+    fn placeholder_names_are_rejected() {
+        let (parameters, errors) = parse("[ticket, task, {name: clipboard}, selected_files]");
+        assert_eq!(parameters, vec![parameter("ticket")]);
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        assert!(errors[0].contains("Parameter name task is reserved"), "{}", errors[0]);
+    }
+
+    #[test]
+    fn front_matter_survives_invalid_parameters() {
+        let (front_matter, _) = crate::skills::parse_skill_md(
+            "---\nname: deploy\ndescription: Deploy\nparameters: 42\n---\n",
+        );
+        assert_eq!(front_matter.name.as_deref(), Some("deploy"));
+        assert!(front_matter.parameters.is_empty());
+    }
+
+    #[test]
+    fn defaults_and_normalisation_are_applied() {
+        let mut env = parameter("env");
+        env.choices = vec!["dev".to_string(), "prod".to_string()];
+        env.default = Some("dev".to_string());
+        let mut dry_run = parameter("dry-run");
+        dry_run.kind = ParameterType::Boolean;
+        let mut count = parameter("count");
+        count.kind = ParameterType::Integer;
+
+        let resolved =
+            validate_parameters(&[env, dry_run, count], &values(&[("dry-run", "YES"), ("count", " 3 ")])).unwrap();
+        assert_eq!(resolved, [Some("dev".to_string()), Some("true".to_string()), Some("3".to_string())]);
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let mut ticket = parameter("ticket");
+        ticket.required = true;
+        let mut env = parameter("env");
+        env.choices = vec!["dev".to_string(), "prod".to_string()];
+        let mut ratio = parameter("ratio");
+        ratio.kind = ParameterType::Number;
+        let mut verbose = parameter("verbose");
+        verbose.kind = ParameterType::Boolean;
+
+        let error = validate_parameters(
+            &[ticket, env, ratio, verbose],
+            &values(&[("env", "qa"), ("ratio", "NaN"), ("verbose", "maybe"), ("extra", "1")]),
+        )
+        .unwrap_err();
+        let lines: Vec<&str> = error.lines().collect();
+        assert_eq!(lines.len(), 5, "{}", error);
+        assert!(lines[0].contains("Unknown parameter extra"));
+        assert!(lines[1].contains("Missing required parameter ticket"));
+        assert!(lines[2].contains("one of: dev, prod"));
+        assert!(lines[3].contains("not a valid number"));
+        assert!(lines[4].contains("true or false"));
+    }
+
+    #[test]
+    fn positional_arguments_keep_gaps_and_drop_trailing_omissions() {
+        let parameters = vec![parameter("a"), parameter("b"), parameter("c"), parameter("d")];
+        let command = build_command(
+            "/run".to_string(),
+            None,
+            &parameters,
+            values(&[("a", "x"), ("c", "two words")]),
+            TemplateInput::default(),
+        )
+        .unwrap();
+        assert_eq!(command, "/run x \"\" \"two words\"");
+    }
+
+    #[test]
+    fn no_arguments_leave_the_invocation_unchanged() {
+        let command =
+            build_command("/run".to_string(), None, &[parameter("a")], HashMap::new(), TemplateInput::default())
+                .unwrap();
+        assert_eq!(command, "/run");
+    }
+
+    #[test]
+    fn template_renders_missing_optionals_as_empty() {
+        let command = build_command(
+            "/deploy".to_string(),
+            Some("/deploy {ticket}{env}".to_string()),
+            &[parameter("ticket"), parameter("env")],
+            values(&[("ticket", "ABC-1")]),
+            TemplateInput::default(),
+        )
+        .unwrap();
+        assert_eq!(command, "/deploy ABC-1");
+    }
+
+    #[test]
+    fn invalid_values_stop_the_build() {
+        let mut count = parameter("count");
+        count.kind = ParameterType::Integer;
+        assert!(build_command(
+            "/run".to_string(),
+            None,
+            &[count],
+            values(&[("count", "1.5")]),
+            TemplateInput::default()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn build_skill_command_uses_the_declaration_on_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = dir.path().join("deploy");
+        std::fs::create_dir(&skill).unwrap();
+        std::fs::write(
+            skill.join("SKILL.md"),
+            "---\nname: deploy\ndescription: Deploy\ncommand-template: /deploy {env}\nparameters:\n  env: {enum: [dev, prod], required: true}\n---\n",
+        )
+        .unwrap();
+        let path = skill.to_string_lossy().to_string();
+
+        let command =
+            build_skill_command("/deploy".to_string(), path.clone(), values(&[("env", "prod")]), TemplateInput::default());
+        assert_eq!(command.unwrap(), "/deploy prod");
+
+        let error =
+            build_skill_command("/deploy".to_string(), path, values(&[("env", "qa")]), TemplateInput::default()).unwrap_err();
+        assert!(error.contains("one of: dev, prod"), "{}", error);
+    }
+
+    #[test]
+    fn build_skill_command_reads_command_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("ship.md");
+        std::fs::write(&file, "---\ndescription: Ship\nparameters: [ticket]\n---\nShip it\n").unwrap();
+
+        let command = build_skill_command(
+            "/ship".to_string(),
+            file.to_string_lossy().to_string(),
+            values(&[("ticket", "ABC-1")]),
+            TemplateInput::default(),
+        );
+        assert_eq!(command.unwrap(), "/ship ABC-1");
+
+        let missing = dir.path().join("missing").to_string_lossy().to_string();
+        assert!(build_skill_command("/x".to_string(), missing, HashMap::new(), TemplateInput::default()).is_err());
+    }
+}
@@ -1,118 +1,22 @@
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
-use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::collections::{HashMap, HashSet};
 
-/// Plugin marketplace configuration
-/// Plugin marketplace 配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[allow(dead_code)] // 预留功能 / Reserved for future use
-struct MarketplaceConfig {
-    #[serde(default)]
-    source: Option<SourceInfo>,
-    install_location: String,
-    #[serde(default)]
-    last_updated: Option<String>,
-    #[serde(default)]
-    auto_update: Option<bool>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[allow(dead_code)] // 预留功能 / Reserved for future use
-struct SourceInfo {
-    source: String,
-    repo: String,
-}
-
-/// Installed plugins configuration
-/// 已安装的 plugins 配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct InstalledPluginsConfig {
-    plugins: std::collections::HashMap<String, Vec<PluginVersion>>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct PluginVersion {
-    scope: String,
-    #[serde(rename = "installPath")]
-    install_path: String,
-    version: String,
-    #[serde(rename = "installedAt")]
-    installed_at: String,
-    #[serde(rename = "lastUpdated")]
-    last_updated: String,
-    #[serde(rename = "gitCommitSha")]
-    git_commit_sha: String,
-}
-
-/// Get marketplace name from marketplace ID
-/// 从 marketplace ID 获取 marketplace 名称
-fn get_marketplace_name(marketplace_id: &str) -> String {
-    match marketplace_id {
-        "happy-claude-skills-gxj" => "Happy Claude".to_string(),
-        "superpowers-marketplace" => "Superpowers".to_string(),
-        "obsidian-skills" => "Obsidian".to_string(),
-        _ => {
-            // 从 ID 中提取友好名称 / Extract friendly name from ID
-            marketplace_id
-                .replace("-", " ")
-                .split_whitespace()
-                .map(|word| {
-                    let mut chars = word.chars();
-                    match chars.next() {
-                        None => String::new(),
-                        Some(first) => first.to_uppercase().chain(chars).collect(),
-                    }
-                })
-                .collect::<Vec<_>>()
-                .join(" ")
-        }
-    }
-}
-
-/// Get plugins directory path
-/// 获取 plugins 目录路径
-fn get_plugins_dir() -> PathBuf {
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    home.join(".claude").join("plugins")
-}
-
-/// Read marketplace configuration
-/// 读取 marketplace 配置
-#[allow(dead_code)] // 预留功能 / Reserved for future use
-fn read_marketplaces_config() -> std::collections::HashMap<String, MarketplaceConfig> {
-    let plugins_dir = get_plugins_dir();
-    let config_path = plugins_dir.join("known_marketplaces.json");
-
-    if !config_path.exists() {
-        return std::collections::HashMap::new();
-    }
-
-    fs::read_to_string(&config_path)
-        .ok()
-        .and_then(|content| serde_json::from_str(&content).ok())
-        .unwrap_or_default()
-}
-
-/// Read installed plugins configuration
-/// 读取已安装的 plugins 配置
-fn read_installed_plugins() -> InstalledPluginsConfig {
-    let plugins_dir = get_plugins_dir();
-    let config_path = plugins_dir.join("installed_plugins.json");
-
-    if !config_path.exists() {
-        return InstalledPluginsConfig {
-            plugins: std::collections::HashMap::new(),
-        };
-    }
+use std::sync::OnceLock;
+use tauri::{Emitter, Manager};
 
-    fs::read_to_string(&config_path)
-        .ok()
-        .and_then(|content| serde_json::from_str(&content).ok())
-        .unwrap_or_else(|| InstalledPluginsConfig {
-            plugins: std::collections::HashMap::new(),
-        })
-}
+use crate::agent_profiles::{AgentProfile, CLAUDE_PROFILE, CODEX_PROFILE};
+use crate::claude_home::{expand_home, get_managed_dir, ClaudeHome};
+use crate::slash_commands::{get_command_directories, scan_command_directory};
+use crate::context::ScanContext;
+use crate::index::SkillIndex;
+use crate::official::{detect_official, skill_content_hash, OfficialStatus};
+use crate::plugins::{build_plugin_ref, canonical_path, get_plugin_skill_directories, read_plugin_manifest, PluginRef, SkillProvenance};
+use crate::settings::{get_extra_skill_paths, LauncherSettings};
+use crate::skill_parameters::{deserialize_parameters, SkillParameter};
+use crate::watcher::KnownSkills;
 
 /// Skill 别名映射表 / Skill alias mapping
 /// 处理 skills 目录名称与插件名称不一致的情况
@@ -142,22 +46,38 @@ fn get_skill_aliases(skill_name: &str) -> Vec<String> {
     aliases
 }
 
-/// Get marketplace for a skill
-/// 获取 skill 的 marketplace
-fn get_skill_marketplace(skill_name: &str, skill_path: &PathBuf) -> String {
-    // 1. 首先检查是否为官方 Anthropic skill / First check if it's an official Anthropic skill
-    if is_official_skill(skill_path) {
-        return "Anthropic".to_string();
+/// Get marketplace, provenance and official status for a skill
+/// 获取 skill 的 marketplace、来源及官方状态
+/// 优先根据文件位置判定，名称别名匹配仅作为最后手段并标记为 guessed；命令和子代理不按名称推测
+/// Location-based matching wins; alias matching is a last resort and flagged as guessed, never used for commands or subagents
+fn get_skill_marketplace(
+    context: &ScanContext,
+    kind: SkillKind,
+    skill_name: &str,
+    skill_path: &Path,
+    content_hash: &str,
+) -> (String, SkillProvenance, Option<OfficialStatus>) {
+    // 1. 首先检查 skill 是否位于某个插件的安装目录内 / First check if the skill lives inside a plugin's installPath
+    if let Some(plugin) = context.find_plugin_for_path(skill_path) {
+        let official = detect_official(context, Some(&plugin), content_hash);
+        return (context.plugin_marketplace_label(&plugin), SkillProvenance::plugin(plugin), official);
     }
 
-    // 2. 然后检查是否从 marketplace 安装 / Then check if installed from marketplace
-    let installed_plugins = read_installed_plugins();
+    // 2. 然后检查内容哈希是否在官方白名单中 / Then check the official content hash allow-list
+    if let Some(official) = detect_official(context, None, content_hash) {
+        return ("Anthropic".to_string(), SkillProvenance::local(), Some(official));
+    }
+
+    if kind != SkillKind::Skill {
+        return ("Local".to_string(), SkillProvenance::local(), None);
+    }
 
+    // 3. 最后按名称别名推测 / Finally guess from name aliases
     // 获取 skill 的所有可能名称（包括别名）/ Get all possible names for skill (including aliases)
     let skill_aliases = get_skill_aliases(skill_name);
 
     // 遍历所有已安装的 plugins，查找匹配的 skill / Iterate through all installed plugins to find matching skill
-    for (plugin_full_name, _versions) in installed_plugins.plugins.iter() {
+    for (plugin_full_name, versions) in context.installed_plugins.plugins.iter() {
         // plugin_full_name 格式: "skill-name@marketplace-id"
         // plugin_full_name format: "skill-name@marketplace-id"
         if let Some(at_pos) = plugin_full_name.find('@') {
@@ -165,136 +85,748 @@ fn get_skill_marketplace(skill_name: &str, skill_path: &PathBuf) -> String {
             let marketplace_id = &plugin_full_name[at_pos + 1..];
 
             // 检查是否匹配任何一个别名 / Check if matches any alias
-            for alias in skill_aliases.iter() {
-                if plugin_skill_name == alias {
-                    return get_marketplace_name(marketplace_id);
-                }
+            if skill_aliases.iter().any(|alias| plugin_skill_name == alias) {
+                let plugin = versions.first().map(|version| {
+                    let manifest = read_plugin_manifest(Path::new(&version.install_path)).unwrap_or_default();
+                    build_plugin_ref(plugin_full_name, version, &manifest)
+                });
+                return (context.marketplace_name(marketplace_id), SkillProvenance::guessed(plugin), None);
             }
         }
     }
 
     // 默认为 Local / Default to Local (表示本地的 skill，不是从 marketplace 安装的)
-    "Local".to_string()
+    ("Local".to_string(), SkillProvenance::local(), None)
+}
+
+/// Kind of item shown in the launcher
+/// 启动器中条目的类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillKind {
+    /// 含 SKILL.md 的 skill 目录 / Skill folder with a SKILL.md
+    #[default]
+    Skill,
+    /// .claude/commands 下的斜杠命令 / Slash command under .claude/commands
+    Command,
+    /// .claude/agents 下的子代理 / Subagent under .claude/agents
+    Agent,
 }
 
 /// Skill representation for frontend
 /// 前端的 Skill 表示
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FrontendSkill {
     pub name: String,
+    /// 嵌套 skill 的中间目录，以 ":" 连接 / Intermediate folders of a nested skill, joined with ":"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
     #[serde(rename = "displayName")]
     pub display_name: String,
     pub description: String,
     pub category: String,
     #[serde(rename = "marketplace")]
     pub marketplace: String,
-    /// Tag标识: "project" 项目技能, "user" 用户技能
-    /// Tag: "project" for project skills, "user" for user skills
+    /// Tag标识: "managed" 组织托管技能, "project" 项目技能, "user" 用户技能, "plugin" 插件技能
+    /// Tag: "managed" for organisation-managed skills, "project" for project skills, "user" for user skills, "plugin" for plugin skills
+    /// 代理配置声明 tag 时使用其 tag，例如 "codex" / An agent profile with its own tag replaces it, e.g. "codex"
     pub tag: String,
+    /// 条目类型 / Item kind
+    #[serde(default)]
+    pub kind: SkillKind,
+    /// 所属代理配置 ID / Agent profile id
+    #[serde(default)]
+    pub agent: String,
     pub path: String,
+    /// 在终端中运行的命令 / Command line run from a shell
     pub command: String,
+    /// 在代理会话中输入的文本 / Text typed inside the agent session
+    #[serde(default)]
+    pub invocation: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// 允许使用的工具 / Tools the skill is allowed to use
+    #[serde(rename = "allowedTools", default)]
+    pub allowed_tools: Vec<String>,
+    /// 目标模型 / Model the skill targets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(rename = "argumentHint", default, skip_serializing_if = "Option::is_none")]
+    pub argument_hint: Option<String>,
+    /// 命令模板，启动时由 render_skill_command 渲染 / Command template, rendered by render_skill_command at launch
+    #[serde(rename = "commandTemplate", default, skip_serializing_if = "Option::is_none")]
+    pub command_template: Option<String>,
+    /// 声明的命名参数，供前端生成表单 / Declared named parameters, used by the frontend to render a form
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<SkillParameter>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 未识别的 Front Matter 字段 / Unrecognised front matter keys
+    #[serde(default)]
+    pub extra: HashMap<String, serde_json::Value>,
+    /// 被当前 skill 覆盖的同名 skills / Same-name skills hidden by this one
+    #[serde(default)]
+    pub overrides: Vec<OverriddenSkill>,
+    /// skill 来源（所属插件及判定方式）/ Where the skill came from and how that was determined
+    pub provenance: SkillProvenance,
+    /// 官方 skill 的判定结果 / Official status and the rule that matched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub official: Option<OfficialStatus>,
 }
 
-/// Get skills directory path
-/// 获取 skills 目录路径
-fn get_skills_dir() -> PathBuf {
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    home.join(".claude").join("skills")
+/// Identity of a launcher item: kind, agent profile id and name
+/// 启动器条目的标识：类型、代理配置 ID 和名称
+/// 同名的 Codex skill 与 Claude skill、skill 与命令互不覆盖 / Same-name Codex and Claude skills, or skills and commands, never hide each other
+pub(crate) type SkillKey = (SkillKind, String, String);
+
+impl FrontendSkill {
+    pub(crate) fn key(&self) -> SkillKey {
+        (self.kind, self.agent.clone(), self.name.clone())
+    }
 }
 
-/// Get project root for skill scanning
-/// 获取用于扫描 skills 的项目根目录
-fn get_project_root() -> Option<PathBuf> {
+/// A same-name skill that lost de-duplication to another entry
+/// 在去重中被覆盖的同名 skill
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OverriddenSkill {
+    pub path: String,
+    pub tag: String,
+}
+
+/// Get the directory the launcher was started for
+/// 获取启动器对应的起始目录
+/// 使用 SKILL_LAUNCHER_PROJECT_ROOT，否则使用当前目录 / SKILL_LAUNCHER_PROJECT_ROOT, else the current dir
+fn get_launch_dir() -> Option<PathBuf> {
     if let Ok(root) = std::env::var("SKILL_LAUNCHER_PROJECT_ROOT") {
         let path = PathBuf::from(root);
         if path.exists() {
-            return Some(path);
+            return Some(std::path::absolute(&path).unwrap_or(path));
         }
     }
 
     std::env::current_dir().ok()
 }
 
+/// Walk upward from start to the project root
+/// 从起始目录向上查找项目根目录
+/// 优先取最近的含 .git 的目录，其次取最近的含 .claude 或 CLAUDE.md 的目录（用户主目录除外）
+/// Prefers the nearest folder with .git, then the nearest with .claude or CLAUDE.md (ignoring the home dir)
+/// Claude 配置目录本身不算项目的 .claude / The Claude config directory itself is not a project .claude
+fn find_project_root(start: &Path, claude_home: &ClaudeHome) -> Option<PathBuf> {
+    let home = dirs::home_dir();
+
+    start
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .or_else(|| {
+            start.ancestors().find(|dir| {
+                let claude_dir = dir.join(".claude");
+                Some(*dir) != home.as_deref()
+                    && ((claude_dir.exists() && claude_dir != claude_home.root()) || dir.join("CLAUDE.md").exists())
+            })
+        })
+        .map(Path::to_path_buf)
+}
+
+/// Get project root for skill scanning
+/// 获取用于扫描 skills 的项目根目录
+pub(crate) fn get_project_root() -> Option<PathBuf> {
+    let start = get_launch_dir()?;
+    Some(find_project_root(&start, &ClaudeHome::resolve()).unwrap_or(start))
+}
+
+/// Search priority of the organisation-managed skills directory
+/// 组织托管 skills 目录的搜索优先级
+/// 托管 skills 由组织统一下发，覆盖项目和用户的同名 skill / Managed skills are set by the organisation and win over project and user skills
+const MANAGED_PRIORITY: i32 = 300;
+
+/// Search priority of project skills directories
+/// 项目 skills 目录的搜索优先级
+const PROJECT_PRIORITY: i32 = 200;
+
+/// Search priority of the user skills directory
+/// 用户 skills 目录的搜索优先级
+const USER_PRIORITY: i32 = 100;
+
+/// A directory searched for skills
+/// 搜索 skills 的目录
+#[derive(Debug, Clone)]
+pub(crate) struct SkillRoot {
+    pub(crate) path: PathBuf,
+    /// "managed"、"project"、"user" 或配置的范围标签 / "managed", "project", "user" or a configured scope label
+    pub(crate) tag: String,
+    pub(crate) priority: i32,
+    /// 是否来自用户配置，配置的目录缺失时需要报告 / Whether it was configured, missing configured dirs are reported
+    pub(crate) configured: bool,
+    /// 代理配置 ID / Agent profile id
+    pub(crate) agent: String,
+}
+
+impl SkillRoot {
+    fn builtin(path: PathBuf, tag: &str, priority: i32) -> Self {
+        SkillRoot {
+            path,
+            tag: tag.to_string(),
+            priority,
+            configured: false,
+            agent: CLAUDE_PROFILE.to_string(),
+        }
+    }
+
+    fn for_agent(mut self, agent: &str) -> Self {
+        self.agent = agent.to_string();
+        self
+    }
+}
+
 /// Get ordered skill directories
 /// 获取按优先级排序的 skills 目录列表
-fn get_skill_directories() -> Vec<PathBuf> {
-    let mut dirs = Vec::new();
+/// 起始目录到项目根目录之间每一层的 .claude/skills 优先，越近优先级越高
+/// .claude/skills folders between the launch dir and the project root come first, nearest first
+/// 配置的额外目录按 priority 合并，同优先级时内置目录在前 / Configured extra dirs are merged by priority, built-in dirs first on ties
+pub(crate) fn get_skill_directories(settings: &LauncherSettings, claude_home: &ClaudeHome) -> Vec<SkillRoot> {
+    let mut dirs = vec![SkillRoot::builtin(
+        get_managed_dir(settings).join("skills"),
+        "managed",
+        MANAGED_PRIORITY,
+    )];
+
+    // 同一目录只保留第一次出现的范围 / Keep the first scope a directory shows up with
+    let mut push_builtin = |root: SkillRoot| {
+        if !dirs.iter().any(|existing| existing.path == root.path) {
+            dirs.push(root);
+        }
+    };
+
+    if let Some(start) = get_launch_dir() {
+        let project_root = find_project_root(&start, claude_home).unwrap_or_else(|| start.clone());
+
+        if start.starts_with(&project_root) {
+            for dir in start.ancestors().take_while(|dir| *dir != project_root) {
+                push_builtin(SkillRoot::builtin(dir.join(".claude").join("skills"), "project", PROJECT_PRIORITY));
+            }
+        }
+
+        push_builtin(SkillRoot::builtin(project_root.join("skills"), "project", PROJECT_PRIORITY));
+        push_builtin(
+            SkillRoot::builtin(project_root.join(".codex").join("skills"), "project", PROJECT_PRIORITY)
+                .for_agent(CODEX_PROFILE),
+        );
+        push_builtin(SkillRoot::builtin(project_root.join(".claude").join("skills"), "project", PROJECT_PRIORITY));
+    }
 
-    if let Some(project_root) = get_project_root() {
-        dirs.push(project_root.join("skills"));
-        dirs.push(project_root.join(".codex").join("skills"));
-        dirs.push(project_root.join(".claude").join("skills"));
+    push_builtin(SkillRoot::builtin(claude_home.skills_dir(), "user", USER_PRIORITY));
+
+    for extra in get_extra_skill_paths(settings) {
+        let path = expand_home(&extra.path);
+        if dirs.iter().any(|root| root.path == path) {
+            continue;
+        }
+        dirs.push(SkillRoot {
+            path,
+            tag: extra.scope,
+            priority: extra.priority,
+            configured: true,
+            agent: extra.agent,
+        });
     }
 
-    dirs.push(get_skills_dir());
+    // 稳定排序，保留同优先级目录的原有顺序 / Stable sort keeps the original order within a priority
+    dirs.sort_by_key(|root| std::cmp::Reverse(root.priority));
 
     dirs
 }
 
-/// Scan a single directory and append skills with de-duplication
-/// 扫描单个目录并去重追加 skills
-/// tag: "project" 项目技能, "user" 用户技能
-fn scan_directory(dir: &PathBuf, tag: &str, skills: &mut Vec<FrontendSkill>, seen: &mut HashSet<String>) -> Result<(), String> {
-    if !dir.exists() {
-        return Ok(());
+/// A path that could not be scanned
+/// 无法扫描的路径
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanError {
+    pub path: String,
+    pub message: String,
+}
+
+/// A skill hidden by a higher-priority skill of the same name
+/// 被更高优先级同名 skill 覆盖的 skill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowedSkill {
+    pub name: String,
+    pub path: String,
+    pub tag: String,
+    /// 生效的 skill 路径 / Path of the skill that won
+    #[serde(rename = "shadowedByPath")]
+    pub shadowed_by_path: String,
+    /// 生效的 skill 的 tag / Tag of the skill that won
+    #[serde(rename = "shadowedByTag")]
+    pub shadowed_by_tag: String,
+}
+
+/// Skills that loaded plus per-path errors
+/// 成功加载的 skills 以及各路径的错误
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanResult {
+    pub skills: Vec<FrontendSkill>,
+    pub errors: Vec<ScanError>,
+    /// 因重名被丢弃的 skills / Skills dropped because of a name collision
+    pub shadowed: Vec<ShadowedSkill>,
+}
+
+impl ScanResult {
+    pub(crate) fn push_error(&mut self, path: &Path, message: String) {
+        eprintln!("⚠️ {}: {}", path.display(), message);
+        self.errors.push(ScanError {
+            path: path.to_string_lossy().to_string(),
+            message,
+        });
     }
 
-    let entries = fs::read_dir(dir)
-        .map_err(|e| format!("无法读取 skills 目录: {}", e))?;
+    /// Append a skill unless a higher-priority skill with the same name is already present
+    /// 追加 skill，已存在更高优先级的同名 skill 时记录为被覆盖
+    /// seen: 条目标识到 skills 下标的映射 / Maps item identity to its index in skills
+    pub(crate) fn push_skill(&mut self, seen: &mut HashMap<SkillKey, usize>, skill: FrontendSkill) {
+        // 同类型、同代理、同名的条目只保留优先级最高的一个，并记录被覆盖的条目
+        // Keep the highest-priority item per kind, agent and name and record the ones it hides
+        match seen.get(&skill.key()) {
+            Some(&winner_index) => {
+                let winner = &mut self.skills[winner_index];
+                winner.overrides.push(OverriddenSkill {
+                    path: skill.path.clone(),
+                    tag: skill.tag.clone(),
+                });
+                self.shadowed.push(ShadowedSkill {
+                    name: skill.name,
+                    path: skill.path,
+                    tag: skill.tag,
+                    shadowed_by_path: winner.path.clone(),
+                    shadowed_by_tag: winner.tag.clone(),
+                });
+            }
+            None => {
+                seen.insert(skill.key(), self.skills.len());
+                self.skills.push(skill);
+            }
+        }
+    }
+}
 
-    for entry in entries {
-        let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
+/// Event emitted after each skills directory is scanned
+/// 每扫描完一个 skills 目录时发出的事件
+pub const SCAN_PROGRESS_EVENT: &str = "skill-scan-progress";
 
-        if !entry.path().is_dir() {
-            continue;
+/// Progress of a running scan, sent once per directory
+/// 扫描进度，每个目录发送一次
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanProgress {
+    /// 刚扫描完的目录 / Directory that was just scanned
+    pub directory: String,
+    /// 已扫描的目录数 / Directories scanned so far
+    #[serde(rename = "directoriesScanned")]
+    pub directories_scanned: usize,
+    /// 需要扫描的目录总数 / Total directories to scan
+    #[serde(rename = "directoriesTotal")]
+    pub directories_total: usize,
+    /// 目前已加载的 skills 数 / Skills loaded so far
+    #[serde(rename = "skillCount")]
+    pub skill_count: usize,
+    /// 该目录新增的 skills / Skills added from this directory
+    pub skills: Vec<FrontendSkill>,
+}
+
+/// Default number of folder levels searched below a skills directory
+/// skills 目录下默认搜索的目录层数
+pub(crate) const DEFAULT_SKILL_SEARCH_DEPTH: usize = 3;
+
+/// A candidate skill folder found below a skills directory
+/// 在 skills 目录下找到的候选 skill 目录
+pub(crate) struct SkillDir {
+    pub(crate) path: PathBuf,
+    /// 中间目录名，例如 skills/<team>/<skill> 中的 team / Intermediate folder names, e.g. team in skills/<team>/<skill>
+    pub(crate) namespace: Vec<String>,
+}
+
+/// Whether a folder contains SKILL.md or skill.md
+/// 目录中是否包含 SKILL.md 或 skill.md
+pub(crate) fn has_skill_md(dir: &Path) -> bool {
+    dir.join("SKILL.md").is_file() || dir.join("skill.md").is_file()
+}
+
+/// Find skill folders below a skills directory, up to max_depth levels deep
+/// 查找 skills 目录下最多 max_depth 层的 skill 目录
+/// 没有 SKILL.md 且其下也没有 skill 的目录同样返回，由调用方决定如何处理
+/// Folders with no SKILL.md and no skills beneath them are returned too, callers decide what to do with them
+pub(crate) fn find_skill_dirs(
+    root: &Path,
+    max_depth: usize,
+    on_error: &mut dyn FnMut(&Path, String),
+) -> Vec<SkillDir> {
+    let mut found = Vec::new();
+    let mut visited = HashSet::new();
+    visited.insert(canonical_path(root));
+    walk_skill_dirs(root, &[], max_depth.max(1), &mut visited, &mut found, on_error);
+    found
+}
+
+/// Recursive step of find_skill_dirs
+/// find_skill_dirs 的递归步骤
+/// visited: 已进入的规范化路径，用于终止符号链接或 junction 循环 / Canonical folders already entered, stops symlink or junction loops
+fn walk_skill_dirs(
+    dir: &Path,
+    namespace: &[String],
+    depth_left: usize,
+    visited: &mut HashSet<PathBuf>,
+    found: &mut Vec<SkillDir>,
+    on_error: &mut dyn FnMut(&Path, String),
+) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            on_error(dir, format!("无法读取 skills 目录 / Unable to read skills directory: {}", e));
+            return;
         }
+    };
 
-        let skill_name = entry.file_name();
-        if let Some(name_str) = skill_name.to_str() {
-            if name_str.starts_with('.') {
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                on_error(dir, format!("读取目录项失败 / Failed to read directory entry: {}", e));
                 continue;
             }
+        };
+
+        let entry_path = entry.path();
+        if !entry_path.is_dir() {
+            continue;
         }
 
-        if let Some(skill) = parse_skill(&entry.path(), tag) {
-            if seen.insert(skill.name.clone()) {
-                skills.push(skill);
-            }
+        let Some(folder_name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if folder_name.starts_with('.') {
+            continue;
         }
+
+        let candidate = SkillDir {
+            path: entry_path.clone(),
+            namespace: namespace.to_vec(),
+        };
+
+        if has_skill_md(&entry_path) || depth_left <= 1 {
+            found.push(candidate);
+            continue;
+        }
+
+        // 跳过已进入过的目录 / Skip folders that were already entered
+        if !visited.insert(canonical_path(&entry_path)) {
+            eprintln!("⚠️ 跳过重复访问的目录 / Skipping already visited folder: {}", entry_path.display());
+            continue;
+        }
+
+        let mut child_namespace = namespace.to_vec();
+        child_namespace.push(folder_name);
+
+        let count = found.len();
+        walk_skill_dirs(&entry_path, &child_namespace, depth_left - 1, visited, found, on_error);
+        if found.len() == count {
+            found.push(candidate);
+        }
+    }
+}
+
+/// A directory queued for scanning
+/// 待扫描的目录
+struct ScanTarget {
+    dir: PathBuf,
+    /// "project" 项目技能, "user" 用户技能, "plugin" 插件技能
+    tag: String,
+    /// 代理配置 ID / Agent profile id
+    agent: String,
+    /// 目录所属的插件，插件 skill 以 "plugin:skill" 命名 / Owning plugin; plugin skills are named "plugin:skill"
+    plugin: Option<PluginRef>,
+}
+
+/// Scan a single directory and append skills with de-duplication
+/// 扫描单个目录并去重追加 skills
+/// 目录项并行解析，结果按目录顺序去重 / Entries are parsed in parallel, then de-duplicated in directory order
+/// 单个目录项失败时记录错误并继续 / Records an error and keeps going when a single entry fails
+/// seen: 条目标识到 result.skills 下标的映射 / Maps item identity to its index in result.skills
+fn scan_directory(
+    context: &ScanContext,
+    index: &mut SkillIndex,
+    target: &ScanTarget,
+    result: &mut ScanResult,
+    seen: &mut HashMap<SkillKey, usize>,
+) {
+    let ScanTarget { dir, tag, agent, plugin } = target;
+    index.add_root(dir);
+    if !dir.exists() {
+        return;
     }
 
-    Ok(())
+    let max_depth = context
+        .settings
+        .skill_search_depth
+        .unwrap_or(DEFAULT_SKILL_SEARCH_DEPTH);
+    let (skill_paths, namespaces): (Vec<PathBuf>, Vec<Vec<String>>) =
+        find_skill_dirs(dir, max_depth, &mut |path, message| result.push_error(path, message))
+            .into_iter()
+            .map(|skill_dir| (skill_dir.path, skill_dir.namespace))
+            .unzip();
+
+    let parsed = index.parse_all(&skill_paths);
+    let skills: Vec<Option<FrontendSkill>> = skill_paths
+        .par_iter()
+        .zip(parsed.into_par_iter())
+        .map(|(skill_path, parsed)| {
+            // Convert OsStr to String / 转换 OsStr 为 String
+            let skill_name = skill_path.file_name()?.to_str()?.to_string();
+            Some(build_skill(context, SkillKind::Skill, skill_path, skill_name, tag, parsed?))
+        })
+        .collect();
+
+    for ((skill_path, namespace), skill) in skill_paths.iter().zip(namespaces).zip(skills) {
+        let Some(mut skill) = skill else {
+            // SKILL.md 存在但无法读取 / SKILL.md exists but could not be read
+            let readme_path = skill_path.join("SKILL.md");
+            if readme_path.exists() {
+                result.push_error(&readme_path, "无法读取 SKILL.md / Unable to read SKILL.md".to_string());
+            }
+            continue;
+        };
+
+        if !namespace.is_empty() {
+            apply_folder_namespace(&mut skill, &namespace);
+        }
+        if let Some(plugin) = plugin {
+            apply_plugin_namespace(context, &mut skill, plugin);
+        }
+        apply_agent_profile(&mut skill, context.agent_profile(agent));
+
+        result.push_skill(seen, skill);
+    }
 }
 
 /// Scan skills directory and return all skills
-/// 扫描 skills 目录并返回所有 skills
+/// 扫描 skills 目录并返回所有 skills，以及无法读取的路径
+/// 在后台线程扫描，并通过 skill-scan-progress 事件逐目录推送结果
+/// Runs on a background thread and streams results per directory via skill-scan-progress
 #[tauri::command]
-pub fn scan_skills_directory() -> Result<Vec<FrontendSkill>, String> {
-    let mut skills = vec![];
-    let mut seen: HashSet<String> = HashSet::new();
-
-    let dirs = get_skill_directories();
-    let user_skills_dir = get_skills_dir();
-
-    for dir in dirs.iter() {
-        // 判断是项目技能还是用户技能
-        // Determine if it's a project skill or user skill
-        let tag = if dir == &user_skills_dir {
-            "user"
-        } else {
-            "project"
-        };
-        scan_directory(dir, tag, &mut skills, &mut seen)?;
+pub async fn scan_skills_directory(app: tauri::AppHandle) -> Result<ScanResult, String> {
+    scan_in_background(app, SkillIndex::load).await
+}
+
+/// Run a scan on a blocking thread, emitting progress events to the frontend
+/// 在阻塞线程上执行扫描，并向前端发送进度事件
+pub(crate) async fn scan_in_background(
+    app: tauri::AppHandle,
+    load_index: fn() -> SkillIndex,
+) -> Result<ScanResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = scan_skills_with_progress(&ScanContext::load(), load_index(), |progress| {
+            if let Err(e) = app.emit(SCAN_PROGRESS_EVENT, progress) {
+                eprintln!("⚠️ 无法发送扫描进度 / Failed to emit scan progress: {}", e);
+            }
+        });
+
+        // 文件监听器以前端看到的结果为基准 / The file watcher diffs against what the frontend has seen
+        if let Some(known) = app.try_state::<KnownSkills>() {
+            known.record(&result.skills);
+        }
+
+        result
+    })
+    .await
+    .map_err(|e| format!("扫描任务失败 / Scan task failed: {}", e))
+}
+
+/// Scan all skill directories using an already loaded scan context
+/// 使用已加载的扫描上下文扫描所有 skills 目录
+pub fn scan_skills(context: &ScanContext) -> ScanResult {
+    scan_skills_with_progress(context, SkillIndex::load(), |_| {})
+}
+
+/// Scan all skill directories, reusing unchanged entries from the given index
+/// 扫描所有 skills 目录，复用索引中未变化的条目
+/// on_progress: 每扫描完一个目录调用一次 / Called once after each directory is scanned
+pub(crate) fn scan_skills_with_progress(
+    context: &ScanContext,
+    mut index: SkillIndex,
+    mut on_progress: impl FnMut(ScanProgress),
+) -> ScanResult {
+    let mut result = ScanResult::default();
+    let mut seen: HashMap<SkillKey, usize> = HashMap::new();
+
+    let mut targets: Vec<ScanTarget> = Vec::new();
+    for root in get_skill_directories(&context.settings, &context.claude_home) {
+        if root.configured && !root.path.is_dir() {
+            result.push_error(
+                &root.path,
+                "配置的 skills 目录不存在 / Configured skills directory does not exist".to_string(),
+            );
+            continue;
+        }
+        targets.push(ScanTarget {
+            dir: root.path,
+            tag: root.tag,
+            agent: root.agent,
+            plugin: None,
+        });
     }
 
-    Ok(skills)
+    // 插件自带的 skills / Skills bundled inside installed plugins
+    let project_root = get_project_root();
+    for (dir, plugin) in get_plugin_skill_directories(&context.installed_plugins, project_root.as_deref()) {
+        targets.push(ScanTarget {
+            dir,
+            tag: "plugin".to_string(),
+            agent: CLAUDE_PROFILE.to_string(),
+            plugin: Some(plugin),
+        });
+    }
+
+    // 斜杠命令和子代理 / Slash commands and subagents
+    let command_roots = get_command_directories(&context.claude_home);
+
+    let directories_total = targets.len() + command_roots.len();
+    for (position, target) in targets.iter().enumerate() {
+        let first_new = result.skills.len();
+        scan_directory(context, &mut index, target, &mut result, &mut seen);
+
+        on_progress(ScanProgress {
+            directory: target.dir.to_string_lossy().to_string(),
+            directories_scanned: position + 1,
+            directories_total,
+            skill_count: result.skills.len(),
+            skills: result.skills[first_new..].to_vec(),
+        });
+    }
+
+    for (position, root) in command_roots.iter().enumerate() {
+        let first_new = result.skills.len();
+        scan_command_directory(context, root, &mut result, &mut seen);
+
+        on_progress(ScanProgress {
+            directory: root.path.to_string_lossy().to_string(),
+            directories_scanned: targets.len() + position + 1,
+            directories_total,
+            skill_count: result.skills.len(),
+            skills: result.skills[first_new..].to_vec(),
+        });
+    }
+
+    index.save();
+
+    result
+}
+
+/// Namespace a nested skill by its intermediate folders ("team:skill")
+/// 以中间目录为嵌套 skill 添加命名空间（"team:skill"）
+pub(crate) fn apply_folder_namespace(skill: &mut FrontendSkill, namespace: &[String]) {
+    let namespace = namespace.join(":");
+    skill.name = format!("{}:{}", namespace, skill.name);
+    skill.namespace = Some(namespace);
+}
+
+/// Namespace a plugin skill the way Claude Code invokes it ("plugin:skill")
+/// 按 Claude Code 的调用方式为插件 skill 添加命名空间（"plugin:skill"）
+fn apply_plugin_namespace(context: &ScanContext, skill: &mut FrontendSkill, plugin: &PluginRef) {
+    skill.name = format!("{}:{}", plugin.name, skill.name);
+    skill.marketplace = context.plugin_marketplace_label(plugin);
+    skill.provenance = SkillProvenance::plugin(plugin.clone());
+}
+
+/// Fill in the tag, command and invocation of a skill from its agent profile
+/// 根据代理配置填写 skill 的 tag、命令和调用文本
+/// 需在命名空间确定后调用 / Call after the skill name is final
+pub(crate) fn apply_agent_profile(skill: &mut FrontendSkill, profile: &AgentProfile) {
+    if let Some(tag) = &profile.tag {
+        skill.tag = tag.clone();
+    }
+    skill.agent = profile.id.clone();
+    skill.command = profile.command_for(&skill.name);
+    skill.invocation = profile.invocation_for(&skill.name);
 }
 
-/// Parse a single skill from its directory
-/// 从目录解析单个 skill
+/// Build a skill from its parsed SKILL.md
+/// 根据解析后的 SKILL.md 构建 skill
 /// tag: "project" 项目技能, "user" 用户技能
-fn parse_skill(skill_path: &PathBuf, tag: &str) -> Option<FrontendSkill> {
-    // Convert OsStr to String / 转换 OsStr 为 String
+pub(crate) fn build_skill(
+    context: &ScanContext,
+    kind: SkillKind,
+    skill_path: &Path,
+    skill_name: String,
+    tag: &str,
+    parsed: ParsedSkill,
+) -> FrontendSkill {
+    // Get marketplace from plugins configuration / 从 plugins 配置获取 marketplace
+    let (marketplace, provenance, official) =
+        get_skill_marketplace(context, kind, &skill_name, skill_path, &parsed.content_hash);
+
+    FrontendSkill {
+        name: skill_name.clone(),
+        namespace: None,
+        display_name: parsed.display_name,
+        description: parsed.description,
+        category: parsed.category,
+        marketplace,
+        tag: tag.to_string(),
+        kind,
+        agent: CLAUDE_PROFILE.to_string(),
+        // Convert Cow<str> to String / 转换 Cow<str> 为 String
+        path: skill_path.to_string_lossy().to_string(),
+        command: format!("claude /{}", skill_name),
+        invocation: format!("/{}", skill_name),
+        license: parsed.license,
+        version: parsed.version,
+        allowed_tools: parsed.allowed_tools,
+        model: parsed.model,
+        argument_hint: parsed.argument_hint,
+        command_template: parsed.command_template,
+        parameters: parsed.parameters,
+        tags: parsed.tags,
+        extra: parsed.extra,
+        overrides: Vec::new(),
+        provenance,
+        official,
+    }
+}
+
+/// SKILL.md contents that do not depend on the scan context
+/// 与扫描上下文无关的 SKILL.md 内容
+/// 保存在 skill 索引中 / Stored in the skill index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ParsedSkill {
+    #[serde(rename = "displayName")]
+    pub(crate) display_name: String,
+    pub(crate) description: String,
+    pub(crate) category: String,
+    #[serde(default)]
+    pub(crate) license: Option<String>,
+    #[serde(default)]
+    pub(crate) version: Option<String>,
+    #[serde(rename = "allowedTools", default)]
+    pub(crate) allowed_tools: Vec<String>,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(rename = "argumentHint", default)]
+    pub(crate) argument_hint: Option<String>,
+    #[serde(rename = "commandTemplate", default)]
+    pub(crate) command_template: Option<String>,
+    #[serde(default)]
+    pub(crate) parameters: Vec<SkillParameter>,
+    #[serde(default)]
+    pub(crate) tags: Vec<String>,
+    #[serde(default)]
+    pub(crate) extra: HashMap<String, serde_json::Value>,
+    /// SKILL.md 内容的 SHA-256 / SHA-256 of the SKILL.md content
+    #[serde(rename = "contentHash")]
+    pub(crate) content_hash: String,
+}
+
+/// Read and parse SKILL.md (or skill.md) from a skill directory
+/// 从 skill 目录读取并解析 SKILL.md（或 skill.md）
+pub(crate) fn read_skill_file(skill_path: &Path) -> Option<ParsedSkill> {
     let skill_name = skill_path.file_name()?.to_str()?.to_string();
 
     // 尝试读取 SKILL.md 或 skill.md / Try to read SKILL.md or skill.md
@@ -305,87 +837,369 @@ fn parse_skill(skill_path: &PathBuf, tag: &str) -> Option<FrontendSkill> {
         .ok()
         .or_else(|| fs::read_to_string(&skill_path_lower).ok())?;
 
-    // Parse Front Matter / 解析 Front Matter
-    let (name, display_name, description) = parse_skill_md(&content);
-    let category = "general".to_string();
+    Some(parse_skill_content(skill_path, skill_name, &content))
+}
 
-    // Get marketplace from plugins configuration / 从 plugins 配置获取 marketplace
-    let marketplace = get_skill_marketplace(&skill_name, skill_path);
+/// Parse markdown with front matter into a ParsedSkill
+/// 将带 Front Matter 的 markdown 解析为 ParsedSkill
+/// fallback_name: 未声明 name 时使用的显示名称 / Display name used when no name is declared
+pub(crate) fn parse_skill_content(path: &Path, fallback_name: String, content: &str) -> ParsedSkill {
+    parsed_skill_from(read_front_matter(path, content), fallback_name, content)
+}
 
-    Some(FrontendSkill {
-        name: skill_name.clone(),
-        display_name: display_name.unwrap_or_else(|| name.unwrap_or_else(|| skill_name.clone())),
-        description,
-        category,
-        marketplace,
-        tag: tag.to_string(),
-        // Convert Cow<str> to String / 转换 Cow<str> 为 String
-        path: skill_path.to_string_lossy().to_string(),
-        command: format!("claude /{}", skill_name),
+/// Parse the front matter of a markdown file, logging a warning when it falls back to line parsing
+/// 解析 markdown 文件的 Front Matter，回退到逐行解析时输出警告
+pub(crate) fn read_front_matter(path: &Path, content: &str) -> SkillFrontMatter {
+    let (front_matter, warning) = parse_skill_md(content);
+    if let Some(warning) = warning {
+        eprintln!("⚠️ {}: {}", path.display(), warning);
+    }
+    front_matter
+}
+
+/// Build a ParsedSkill from already parsed front matter
+/// 根据已解析的 Front Matter 构建 ParsedSkill
+pub(crate) fn parsed_skill_from(front_matter: SkillFrontMatter, fallback_name: String, content: &str) -> ParsedSkill {
+    ParsedSkill {
+        display_name: front_matter
+            .display_name
+            .or(front_matter.name)
+            .unwrap_or(fallback_name),
+        description: front_matter.description.unwrap_or_default(),
+        category: front_matter
+            .category
+            .unwrap_or_else(|| "general".to_string()),
+        license: front_matter.license,
+        version: front_matter.version,
+        allowed_tools: front_matter.allowed_tools,
+        model: front_matter.model,
+        argument_hint: front_matter.argument_hint,
+        command_template: front_matter.command_template,
+        parameters: front_matter.parameters,
+        tags: front_matter.tags,
+        extra: front_matter
+            .extra
+            .iter()
+            .filter_map(|(key, value)| Some((key.clone(), serde_json::to_value(value).ok()?)))
+            .collect(),
+        content_hash: skill_content_hash(content),
+    }
+}
+
+/// SKILL.md Front Matter fields
+/// SKILL.md 的 Front Matter 字段
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SkillFrontMatter {
+    #[serde(default, deserialize_with = "deserialize_scalar")]
+    pub name: Option<String>,
+    #[serde(default, alias = "displayName", deserialize_with = "deserialize_scalar")]
+    pub display_name: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_scalar")]
+    pub description: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_scalar")]
+    pub category: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_scalar")]
+    pub license: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_scalar")]
+    pub version: Option<String>,
+    #[serde(
+        default,
+        rename = "allowed-tools",
+        alias = "allowed_tools",
+        alias = "allowedTools",
+        alias = "tools",
+        deserialize_with = "deserialize_string_list"
+    )]
+    pub allowed_tools: Vec<String>,
+    #[serde(default, deserialize_with = "deserialize_scalar")]
+    pub model: Option<String>,
+    #[serde(
+        default,
+        rename = "argument-hint",
+        alias = "argument_hint",
+        alias = "argumentHint",
+        deserialize_with = "deserialize_scalar"
+    )]
+    pub argument_hint: Option<String>,
+    /// 命令模板，例如 "/review {git_branch} against main" / Command template, e.g. "/review {git_branch} against main"
+    #[serde(
+        default,
+        rename = "command-template",
+        alias = "command_template",
+        alias = "commandTemplate",
+        deserialize_with = "deserialize_scalar"
+    )]
+    pub command_template: Option<String>,
+    /// 命名参数，可写成列表或映射 / Named parameters, as a list or a mapping
+    #[serde(default, deserialize_with = "deserialize_parameters")]
+    pub parameters: Vec<SkillParameter>,
+    #[serde(default, deserialize_with = "deserialize_string_list")]
+    pub tags: Vec<String>,
+    /// 其他未识别的字段 / Any other keys not listed above
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_yaml::Value>,
+}
+
+/// Accept any YAML scalar (string, number, bool) as a trimmed string
+/// 接受任意 YAML 标量（字符串、数字、布尔值）并转换为去除首尾空白的字符串
+pub(crate) fn deserialize_scalar<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_yaml::Value>::deserialize(deserializer)?;
+    Ok(value.and_then(|value| scalar_to_string(&value)))
+}
+
+/// Accept either a YAML list or a comma separated string
+/// 接受 YAML 列表或逗号分隔的字符串
+pub(crate) fn deserialize_string_list<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_yaml::Value>::deserialize(deserializer)?;
+    Ok(match value {
+        Some(serde_yaml::Value::Sequence(items)) => {
+            items.iter().filter_map(scalar_to_string).collect()
+        }
+        Some(value) => scalar_to_string(&value)
+            .map(|text| split_list(&text))
+            .unwrap_or_default(),
+        None => Vec::new(),
     })
 }
 
-/// Parse SKILL.md Front Matter - 只提取 description 字段
-/// 解析 SKILL.md 的 Front Matter - 仅提取 description 字段
+/// Split a comma separated list, dropping empty items
+/// 拆分逗号分隔的列表并丢弃空项
+fn split_list(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Convert a YAML scalar to a string, ignoring empty values
+/// 将 YAML 标量转换为字符串，忽略空值
+fn scalar_to_string(value: &serde_yaml::Value) -> Option<String> {
+    let text = match value {
+        serde_yaml::Value::String(s) => s.trim().to_string(),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        _ => return None,
+    };
+
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// Extract the raw Front Matter block from SKILL.md
+/// 从 SKILL.md 中提取原始 Front Matter 块
 /// 支持Windows(CRLF)和Unix(LF)换行符 / Supports both Windows (CRLF) and Unix (LF) line endings
-fn parse_skill_md(content: &str) -> (Option<String>, Option<String>, String) {
-    // 使用 \r?\n 匹配两种换行格式 / Use \r?\n to match both line ending formats
-    let front_matter_regex = regex::Regex::new(r"^---\r?\n([\s\S]+?)\r?\n---").unwrap();
-
-    let mut name = None;
-    let mut display_name = None;
-    let mut description = None;
-
-    if let Some(caps) = front_matter_regex.captures(content) {
-        for line in caps[1].split('\n') {
-            if let Some((key, value)) = line.split_once(':') {
-                let key = key.trim();
-                let value = value.trim();
-
-                match key {
-                    "name" => name = Some(value.to_string()),
-                    "display_name" | "displayName" => {
-                        display_name = Some(value.trim_matches('"').to_string());
-                    }
-                    "description" => {
-                        // 移除外围引号 / Remove surrounding quotes
-                        description = Some(value.trim_matches('"').to_string());
-                    }
-                    _ => {}
-                }
+pub(crate) fn extract_front_matter(content: &str) -> Option<&str> {
+    // 使用 \r?\n 匹配两种换行格式，正则只编译一次 / Use \r?\n to match both line ending formats, compiled once
+    static FRONT_MATTER_REGEX: OnceLock<regex::Regex> = OnceLock::new();
+    let front_matter_regex =
+        FRONT_MATTER_REGEX.get_or_init(|| regex::Regex::new(r"^---\r?\n([\s\S]+?)\r?\n---").unwrap());
+
+    front_matter_regex
+        .captures(content)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str())
+}
+
+/// Parse SKILL.md Front Matter as YAML
+/// 将 SKILL.md 的 Front Matter 按 YAML 解析
+/// 返回解析结果和可选的警告信息；YAML 无效时回退到逐行解析
+/// Returns the parsed fields and an optional warning; falls back to line-based parsing when the YAML is invalid
+pub fn parse_skill_md(content: &str) -> (SkillFrontMatter, Option<String>) {
+    let Some(raw) = extract_front_matter(content) else {
+        return (SkillFrontMatter::default(), None);
+    };
+
+    match serde_yaml::from_str::<Option<SkillFrontMatter>>(raw) {
+        Ok(front_matter) => (front_matter.unwrap_or_default(), None),
+        Err(e) => (
+            parse_front_matter_lines(raw),
+            Some(format!("Front Matter 不是有效的 YAML，已回退到逐行解析 / Invalid YAML front matter, fell back to line parsing: {}", e)),
+        ),
+    }
+}
+
+/// Whether a value is a YAML block scalar indicator such as "|", ">-" or "|+"
+/// 值是否为 YAML 块标量指示符，例如 "|"、">-" 或 "|+"
+fn is_block_scalar_indicator(value: &str) -> bool {
+    let mut chars = value.chars();
+    matches!(chars.next(), Some('|' | '>'))
+        && chars.all(|c| matches!(c, '-' | '+' | '1'..='9'))
+}
+
+/// Join the lines of a block scalar: literal keeps line breaks, folded joins lines with spaces
+/// 拼接块标量的各行：literal 保留换行，folded 以空格连接，空行表示换行
+fn join_block_scalar(lines: &[&str], literal: bool) -> String {
+    if literal {
+        return lines.join("\n").trim().to_string();
+    }
+
+    let mut text = String::new();
+    for line in lines {
+        if line.is_empty() {
+            text.push('\n');
+        } else {
+            if !text.is_empty() && !text.ends_with('\n') {
+                text.push(' ');
             }
+            text.push_str(line);
         }
     }
-
-    // 返回 name, display_name, description / Return name, display_name, description
-    (name, display_name, description.unwrap_or_default())
+    text.trim().to_string()
 }
 
-/// Check if a skill is an official Anthropic skill by reading its SKILL.md
-/// 通过读取 SKILL.md 检查是否为 Anthropic 官方 skill
-/// 支持Windows(CRLF)和Unix(LF)换行符 / Supports both Windows (CRLF) and Unix (LF) line endings
-fn is_official_skill(skill_path: &PathBuf) -> bool {
-    let readme_path = skill_path.join("SKILL.md");
+/// Line-based fallback parser for malformed Front Matter
+/// 针对格式错误的 Front Matter 的逐行回退解析
+fn parse_front_matter_lines(raw: &str) -> SkillFrontMatter {
+    let mut front_matter = SkillFrontMatter::default();
+    let mut lines = raw.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        // 只处理顶层键 / Only handle top-level keys
+        if line.starts_with([' ', '\t', '-', '#']) {
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once(':') {
+            let value = value.trim();
+
+            // 块标量（| 或 >）的内容在后续缩进行中 / Block scalars (| or >) continue on the indented lines that follow
+            let value = if is_block_scalar_indicator(value) {
+                let mut block = Vec::new();
+                while let Some(next) = lines.next_if(|next| next.trim().is_empty() || next.starts_with([' ', '\t'])) {
+                    block.push(next.trim());
+                }
+                join_block_scalar(&block, value.starts_with('|'))
+            } else {
+                // 移除外围引号 / Remove surrounding quotes
+                value.trim_matches('"').trim().to_string()
+            };
+            if value.is_empty() {
+                continue;
+            }
 
-    if let Ok(content) = fs::read_to_string(&readme_path) {
-        // Check for license in front matter / 检查 front matter 中的 license
-        // 使用 \r?\n 匹配两种换行格式 / Use \r?\n to match both line ending formats
-        let front_matter_regex = regex::Regex::new(r"^---\r?\n([\s\S]+?)\r?\n---").unwrap();
-
-        if let Some(caps) = front_matter_regex.captures(&content) {
-            for line in caps[1].split('\n') {
-                if let Some((key, value)) = line.split_once(':') {
-                    if key.trim() == "license" {
-                        let license = value.trim().to_lowercase();
-                        // Official skills have proprietary licenses / 官方 skills 使用专有许可证
-                        return license.contains("proprietary") ||
-                               license.contains("license.txt") ||
-                               license.contains("complete terms");
-                    }
+            match key.trim() {
+                "name" => front_matter.name = Some(value),
+                "display_name" | "displayName" => front_matter.display_name = Some(value),
+                "description" => front_matter.description = Some(value),
+                "category" => front_matter.category = Some(value),
+                "license" => front_matter.license = Some(value),
+                "version" => front_matter.version = Some(value),
+                "allowed-tools" | "allowed_tools" | "allowedTools" => {
+                    front_matter.allowed_tools = split_list(&value);
+                }
+                "model" => front_matter.model = Some(value),
+                "argument-hint" | "argument_hint" | "argumentHint" => {
+                    front_matter.argument_hint = Some(value);
+                }
+                "command-template" | "command_template" | "commandTemplate" => {
+                    front_matter.command_template = Some(value);
+                }
+                "tags" => front_matter.tags = split_list(value.trim_matches(['[', ']'])),
+                key => {
+                    front_matter
+                        .extra
+                        .insert(key.to_string(), serde_yaml::Value::String(value));
                 }
             }
         }
     }
 
-    false
+    front_matter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestEnv;
+
+    /// 以无效的 YAML 行强制走逐行回退解析 / An invalid YAML line forces the line-based fallback
+    fn parse_fallback(body: &str) -> SkillFrontMatter {
+        let content = format!("---\nname: demo\ntitle: a: b\n{}\n---\n", body);
+        let (front_matter, warning) = parse_skill_md(&content);
+        assert!(warning.is_some(), "expected the line-based fallback");
+        front_matter
+    }
+
+    #[test]
+    fn valid_yaml_has_no_warning() {
+        let (front_matter, warning) = parse_skill_md("---\nname: demo\ndescription: >\n  One\n  two\n---\n");
+        assert!(warning.is_none());
+        assert_eq!(front_matter.description.as_deref(), Some("One two"));
+    }
+
+    #[test]
+    fn malformed_yaml_falls_back_to_line_parser() {
+        let front_matter = parse_fallback("description: \"Quoted text\"\nallowed-tools: Read, Grep");
+        assert_eq!(front_matter.name.as_deref(), Some("demo"));
+        assert_eq!(front_matter.description.as_deref(), Some("Quoted text"));
+        assert_eq!(front_matter.allowed_tools, ["Read", "Grep"]);
+        assert_eq!(front_matter.extra.get("title"), Some(&serde_yaml::Value::String("a: b".to_string())));
+    }
+
+    #[test]
+    fn folded_description_joins_lines() {
+        let front_matter = parse_fallback("description: >\n  Folded line one\n  and two\n\n  New paragraph\nversion: 1.0");
+        assert_eq!(front_matter.description.as_deref(), Some("Folded line one and two\nNew paragraph"));
+        assert_eq!(front_matter.version.as_deref(), Some("1.0"));
+    }
+
+    #[test]
+    fn literal_description_keeps_line_breaks() {
+        let front_matter = parse_fallback("description: |\n  Line one\n  Line two\nlicense: MIT");
+        assert_eq!(front_matter.description.as_deref(), Some("Line one\nLine two"));
+        assert_eq!(front_matter.license.as_deref(), Some("MIT"));
+    }
+
+    #[test]
+    fn chomping_indicators_are_block_scalars() {
+        let front_matter = parse_fallback("description: |-\n  Stripped\n  text\ncategory: >+\n  Kept\n  text\n");
+        assert_eq!(front_matter.description.as_deref(), Some("Stripped\ntext"));
+        assert_eq!(front_matter.category.as_deref(), Some("Kept text"));
+    }
+
+    #[test]
+    fn crlf_line_endings_are_handled() {
+        let content = "---\r\nname: demo\r\ntitle: a: b\r\ndescription: >-\r\n  CRLF\r\n  folded\r\n---\r\n";
+        let (front_matter, warning) = parse_skill_md(content);
+        assert!(warning.is_some());
+        assert_eq!(front_matter.name.as_deref(), Some("demo"));
+        assert_eq!(front_matter.description.as_deref(), Some("CRLF folded"));
+    }
+
+    #[test]
+    fn codex_and_claude_skills_with_the_same_name_both_survive() {
+        let env = TestEnv::new();
+        let content = "---\nname: review\ndescription: Review\n---\n";
+        env.write(env.project().join(".codex").join("skills").join("review").join("SKILL.md"), content);
+        env.write(env.project().join(".claude").join("skills").join("review").join("SKILL.md"), content);
+
+        let result = scan_skills(&ScanContext::load());
+        let mut reviews: Vec<(&str, &str)> = result
+            .skills
+            .iter()
+            .filter(|skill| skill.name == "review")
+            .map(|skill| (skill.agent.as_str(), skill.invocation.as_str()))
+            .collect();
+        reviews.sort();
+        assert_eq!(reviews, [("claude", "/review"), ("codex", "$review")]);
+        assert!(result.shadowed.is_empty());
+    }
+
+    #[test]
+    fn block_scalar_indicator_requires_a_bare_indicator() {
+        assert!(is_block_scalar_indicator("|"));
+        assert!(is_block_scalar_indicator(">-"));
+        assert!(is_block_scalar_indicator("|2+"));
+        assert!(!is_block_scalar_indicator("> quoted"));
+        assert!(!is_block_scalar_indicator("plain"));
+    }
 }
@@ -0,0 +1,240 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::agent_profiles::CLAUDE_PROFILE;
+use crate::claude_home::ClaudeHome;
+use crate::context::ScanContext;
+use crate::plugins::canonical_path;
+use crate::skills::{
+    apply_agent_profile, apply_folder_namespace, build_skill, extract_front_matter, get_project_root, parsed_skill_from,
+    read_front_matter, ScanResult, SkillKey, SkillKind, DEFAULT_SKILL_SEARCH_DEPTH,
+};
+
+/// A directory searched for slash commands or subagents
+/// 搜索斜杠命令或子代理的目录
+#[derive(Debug, Clone)]
+pub(crate) struct CommandRoot {
+    pub(crate) path: PathBuf,
+    /// "project" 或 "user" / "project" or "user"
+    pub(crate) tag: String,
+    /// Command 或 Agent / Command or Agent
+    pub(crate) kind: SkillKind,
+}
+
+/// Get slash command and subagent directories, project scope first
+/// 获取斜杠命令和子代理目录，项目范围在前
+pub(crate) fn get_command_directories(claude_home: &ClaudeHome) -> Vec<CommandRoot> {
+    let mut roots = Vec::new();
+
+    let mut push = |base: &Path, tag: &str| {
+        roots.push(CommandRoot {
+            path: base.join("commands"),
+            tag: tag.to_string(),
+            kind: SkillKind::Command,
+        });
+        roots.push(CommandRoot {
+            path: base.join("agents"),
+            tag: tag.to_string(),
+            kind: SkillKind::Agent,
+        });
+    };
+
+    if let Some(project_root) = get_project_root() {
+        push(&project_root.join(".claude"), "project");
+    }
+    push(claude_home.root(), "user");
+
+    roots
+}
+
+/// Find markdown files below a commands or agents directory, namespaced by subfolder
+/// 查找 commands 或 agents 目录下的 markdown 文件，子目录作为命名空间
+pub(crate) fn find_markdown_files(
+    root: &Path,
+    max_depth: usize,
+    on_error: &mut dyn FnMut(&Path, String),
+) -> Vec<(PathBuf, Vec<String>)> {
+    let mut found = Vec::new();
+    let mut visited = HashSet::new();
+    visited.insert(canonical_path(root));
+    walk_markdown_files(root, &[], max_depth.max(1), &mut visited, &mut found, on_error);
+    found
+}
+
+/// Recursive step of find_markdown_files
+/// find_markdown_files 的递归步骤
+/// visited: 已进入的规范化路径，用于终止符号链接循环 / Canonical folders already entered, stops symlink loops
+fn walk_markdown_files(
+    dir: &Path,
+    namespace: &[String],
+    depth_left: usize,
+    visited: &mut HashSet<PathBuf>,
+    found: &mut Vec<(PathBuf, Vec<String>)>,
+    on_error: &mut dyn FnMut(&Path, String),
+) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            on_error(dir, format!("无法读取命令目录 / Unable to read commands directory: {}", e));
+            return;
+        }
+    };
+
+    let mut entries: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect();
+    entries.sort();
+
+    for entry_path in entries {
+        let Some(file_name) = entry_path.file_name().and_then(|name| name.to_str()).map(str::to_string) else {
+            continue;
+        };
+        if file_name.starts_with('.') {
+            continue;
+        }
+
+        if entry_path.is_dir() {
+            if depth_left <= 1 || !visited.insert(canonical_path(&entry_path)) {
+                continue;
+            }
+            let mut child_namespace = namespace.to_vec();
+            child_namespace.push(file_name);
+            walk_markdown_files(&entry_path, &child_namespace, depth_left - 1, visited, found, on_error);
+        } else if entry_path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("md")) {
+            found.push((entry_path, namespace.to_vec()));
+        }
+    }
+}
+
+/// First non-empty line after the front matter, used when no description is declared
+/// Front Matter 之后的第一行非空内容，未声明 description 时使用
+fn first_body_line(content: &str) -> String {
+    let body = match extract_front_matter(content) {
+        // 跳过 Front Matter 及结束的 --- / Skip the front matter and its closing ---
+        Some(raw) => {
+            let end = content.find(raw).map_or(0, |start| start + raw.len());
+            content[end..].trim_start_matches(['\r', '\n']).strip_prefix("---").unwrap_or("")
+        }
+        None => content,
+    };
+
+    body.lines()
+        .map(|line| line.trim().trim_start_matches('#').trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Scan a commands or agents directory and append its items with de-duplication
+/// 扫描 commands 或 agents 目录并去重追加条目
+/// 命令以 "/ns:name" 调用，子代理以 "@agent-name" 调用
+/// Commands are invoked as "/ns:name", subagents as "@agent-name"
+pub(crate) fn scan_command_directory(
+    context: &ScanContext,
+    root: &CommandRoot,
+    result: &mut ScanResult,
+    seen: &mut HashMap<SkillKey, usize>,
+) {
+    if !root.path.is_dir() {
+        return;
+    }
+
+    let max_depth = context
+        .settings
+        .skill_search_depth
+        .unwrap_or(DEFAULT_SKILL_SEARCH_DEPTH);
+    let files = find_markdown_files(&root.path, max_depth, &mut |path, message| result.push_error(path, message));
+
+    for (file_path, namespace) in files {
+        let Some(stem) = file_path.file_stem().and_then(|stem| stem.to_str()).map(str::to_string) else {
+            continue;
+        };
+
+        let content = match fs::read_to_string(&file_path) {
+            Ok(content) => content,
+            Err(e) => {
+                result.push_error(&file_path, format!("无法读取文件 / Unable to read file: {}", e));
+                continue;
+            }
+        };
+
+        let front_matter = read_front_matter(&file_path, &content);
+        // 子代理以 Front Matter 中的 name 为准 / Subagents are identified by their front matter name
+        let declared_name = front_matter.name.clone().filter(|name| !name.is_empty());
+        let mut parsed = parsed_skill_from(front_matter, stem.clone(), &content);
+        if parsed.description.is_empty() {
+            parsed.description = first_body_line(&content);
+        }
+
+        let skill = match root.kind {
+            SkillKind::Agent => {
+                let name = format!("@agent-{}", declared_name.unwrap_or(stem));
+                let mut skill = build_skill(context, root.kind, &file_path, name, &root.tag, parsed);
+                skill.command = format!("claude {}", skill.name);
+                skill.invocation = skill.name.clone();
+                skill
+            }
+            _ => {
+                let mut skill = build_skill(context, root.kind, &file_path, stem, &root.tag, parsed);
+                if !namespace.is_empty() {
+                    apply_folder_namespace(&mut skill, &namespace);
+                }
+                apply_agent_profile(&mut skill, context.agent_profile(CLAUDE_PROFILE));
+                skill
+            }
+        };
+
+        result.push_skill(seen, skill);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::ProvenanceSource;
+    use crate::skills::scan_skills;
+    use crate::test_support::TestEnv;
+
+    #[test]
+    fn agents_use_their_front_matter_name() {
+        let env = TestEnv::new();
+        env.write(
+            env.project().join(".claude").join("agents").join("reviewer.md"),
+            "---\nname: code-reviewer\ndescription: Reviews code\n---\n",
+        );
+
+        let result = scan_skills(&ScanContext::load());
+        let agent = result.skills.iter().find(|skill| skill.kind == SkillKind::Agent).unwrap();
+        assert_eq!(agent.name, "@agent-code-reviewer");
+        assert_eq!(agent.invocation, "@agent-code-reviewer");
+        assert_eq!(agent.description, "Reviews code");
+    }
+
+    #[test]
+    fn commands_never_get_a_guessed_marketplace() {
+        let env = TestEnv::new();
+        let plugins_dir = env.claude_home().join("plugins");
+        env.write(
+            plugins_dir.join("installed_plugins.json"),
+            &serde_json::json!({
+                "version": 2,
+                "plugins": { "review@tools": [{ "scope": "user", "installPath": plugins_dir.join("cache").join("review"), "version": "1.0.0" }] }
+            })
+            .to_string(),
+        );
+        env.write(env.claude_home().join("commands").join("review.md"), "Review the diff\n");
+        env.write(
+            env.claude_home().join("skills").join("review").join("SKILL.md"),
+            "---\nname: review\ndescription: Review skill\n---\n",
+        );
+
+        let result = scan_skills(&ScanContext::load());
+        let command = result.skills.iter().find(|skill| skill.kind == SkillKind::Command).unwrap();
+        assert_eq!(command.name, "review");
+        assert_eq!(command.marketplace, "Local");
+        assert_eq!(command.provenance.source, ProvenanceSource::Local);
+
+        // skill 仍按名称推测 / Skills are still guessed by name
+        let skill = result.skills.iter().find(|skill| skill.kind == SkillKind::Skill).unwrap();
+        assert_eq!(skill.provenance.source, ProvenanceSource::Guessed);
+    }
+}
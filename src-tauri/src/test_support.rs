@@ -0,0 +1,55 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+
+/// Serialises tests that point the launcher at a temp dir through environment variables
+/// 串行化通过环境变量把启动器指向临时目录的测试
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// Temp Claude home, project and settings, so tests never read the host's configuration
+/// 临时的 Claude 目录、项目和设置，测试不会读取本机配置
+/// 持有期间其他使用 TestEnv 的测试会等待 / Other tests using TestEnv wait while one is alive
+pub(crate) struct TestEnv {
+    dir: tempfile::TempDir,
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl TestEnv {
+    pub(crate) fn new() -> Self {
+        let guard = ENV_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let dir = tempfile::tempdir().unwrap();
+        let env = TestEnv { dir, _guard: guard };
+
+        // .git 固定项目根目录 / .git pins the project root
+        fs::create_dir_all(env.project().join(".git")).unwrap();
+        fs::create_dir_all(env.claude_home()).unwrap();
+        let settings = env.dir.path().join("settings.json");
+        fs::write(
+            &settings,
+            serde_json::json!({ "managedConfigDir": env.dir.path().join("managed") }).to_string(),
+        )
+        .unwrap();
+
+        std::env::set_var("SKILL_LAUNCHER_SETTINGS", &settings);
+        std::env::set_var("SKILL_LAUNCHER_PROJECT_ROOT", env.project());
+        std::env::set_var("SKILL_LAUNCHER_INDEX", env.dir.path().join("skill-index.json"));
+        std::env::set_var("CLAUDE_CONFIG_DIR", env.claude_home());
+        env
+    }
+
+    pub(crate) fn project(&self) -> PathBuf {
+        self.dir.path().join("project")
+    }
+
+    pub(crate) fn claude_home(&self) -> PathBuf {
+        self.dir.path().join("claude")
+    }
+
+    /// Write a markdown file with the given content, creating parent folders
+    /// 写入 markdown 文件，并创建上级目录
+    pub(crate) fn write(&self, path: PathBuf, content: &str) -> PathBuf {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+}
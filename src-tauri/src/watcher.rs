@@ -0,0 +1,228 @@
+use notify_debouncer_mini::notify::{RecursiveMode, Watcher};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::claude_home::ClaudeHome;
+use crate::slash_commands::get_command_directories;
+use crate::plugins::{get_plugin_skill_directories, read_installed_plugins};
+use crate::context::ScanContext;
+use crate::settings::load_settings;
+use crate::skills::{get_project_root, get_skill_directories, scan_skills, FrontendSkill, SkillKey};
+
+/// Event emitted when a new skill appears
+/// 新增 skill 时发出的事件
+pub const SKILL_ADDED_EVENT: &str = "skill-added";
+
+/// Event emitted when an existing skill changes
+/// 已有 skill 发生变化时发出的事件
+pub const SKILL_UPDATED_EVENT: &str = "skill-updated";
+
+/// Event emitted when a skill disappears
+/// skill 被删除时发出的事件
+pub const SKILL_REMOVED_EVENT: &str = "skill-removed";
+
+/// Quiet period before a burst of file changes triggers a rescan
+/// 文件连续变化后等待多久再重新扫描
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Skills the frontend currently shows, keyed by kind, agent and name
+/// 前端当前显示的 skills，按类型、代理和名称索引
+/// 由前端的扫描写入，监听器以此为基准比较变化，启动时不必再完整扫描一次
+/// Recorded by the frontend's scans so the watcher diffs against them instead of running its own startup scan
+#[derive(Default)]
+pub struct KnownSkills(Mutex<Option<HashMap<SkillKey, FrontendSkill>>>);
+
+impl KnownSkills {
+    /// Replace the known skills with the result of a scan
+    /// 用一次扫描的结果替换已知的 skills
+    pub(crate) fn record(&self, skills: &[FrontendSkill]) {
+        if let Ok(mut known) = self.0.lock() {
+            *known = Some(by_key(skills.to_vec()));
+        }
+    }
+}
+
+/// A watched path and the path whose changes matter
+/// 监听的路径以及需要关注变化的路径
+/// 对尚不存在的目录监听的是其上级目录，只有通往该目录的变化才相关
+/// Missing directories are watched through an ancestor, where only changes leading to the directory matter
+struct WatchTarget {
+    path: PathBuf,
+    mode: RecursiveMode,
+    target: PathBuf,
+}
+
+/// Paths to watch and whether to watch them recursively
+/// 需要监听的路径以及是否递归监听
+/// 尚不存在的 skills 目录改为监听其最近的已存在上级目录，以便发现新建的目录
+/// Missing skills directories are replaced by their nearest existing ancestor so new folders are noticed
+fn watch_targets() -> Vec<WatchTarget> {
+    let mut targets = Vec::new();
+
+    let settings = load_settings();
+    let claude_home = ClaudeHome::from_settings(&settings);
+    let installed_plugins = read_installed_plugins(&claude_home);
+    let project_root = get_project_root();
+    let plugin_dirs = get_plugin_skill_directories(&installed_plugins, project_root.as_deref())
+        .into_iter()
+        .map(|(dir, _)| dir);
+
+    let skill_dirs = get_skill_directories(&settings, &claude_home)
+        .into_iter()
+        .map(|root| root.path);
+    let command_dirs = get_command_directories(&claude_home).into_iter().map(|root| root.path);
+
+    for dir in skill_dirs.chain(plugin_dirs).chain(command_dirs) {
+        if dir.is_dir() {
+            targets.push(WatchTarget {
+                path: dir.clone(),
+                mode: RecursiveMode::Recursive,
+                target: dir,
+            });
+        } else if let Some(ancestor) = dir.ancestors().skip(1).find(|path| path.is_dir()) {
+            targets.push(WatchTarget {
+                path: ancestor.to_path_buf(),
+                mode: RecursiveMode::NonRecursive,
+                target: dir,
+            });
+        }
+    }
+
+    // installed_plugins.json 可能被整体替换，因此监听其所在目录
+    // installed_plugins.json may be replaced wholesale, so watch its folder instead
+    let plugins_dir = claude_home.plugins_dir();
+    if plugins_dir.is_dir() {
+        targets.push(WatchTarget {
+            target: plugins_dir.join("installed_plugins.json"),
+            path: plugins_dir,
+            mode: RecursiveMode::NonRecursive,
+        });
+    }
+
+    targets
+}
+
+/// Register watches for targets that are not watched yet, returning the paths whose changes matter
+/// 为尚未监听的路径注册监听，返回需要关注变化的路径
+fn update_watches(watcher: &mut dyn Watcher, watched: &mut HashSet<(PathBuf, bool)>) -> Vec<PathBuf> {
+    let mut relevant = Vec::new();
+
+    for WatchTarget { path, mode, target } in watch_targets() {
+        relevant.push(target);
+
+        let key = (path, mode == RecursiveMode::Recursive);
+        if watched.contains(&key) {
+            continue;
+        }
+
+        match watcher.watch(&key.0, mode) {
+            Ok(()) => {
+                watched.insert(key);
+            }
+            Err(e) => eprintln!("⚠️ 无法监听 / Failed to watch {}: {}", key.0.display(), e),
+        }
+    }
+
+    relevant
+}
+
+/// Index skills by kind, agent and name, a command or a Codex skill may share its name with a Claude skill
+/// 按类型、代理和名称索引 skills，命令或 Codex skill 可以与 Claude skill 同名
+fn by_key(skills: Vec<FrontendSkill>) -> HashMap<SkillKey, FrontendSkill> {
+    skills.into_iter().map(|skill| (skill.key(), skill)).collect()
+}
+
+/// Emit added, updated and removed events between two scans
+/// 比较两次扫描结果并发出新增、更新和删除事件
+fn emit_changes<R: Runtime>(
+    app: &AppHandle<R>,
+    previous: &HashMap<SkillKey, FrontendSkill>,
+    current: &HashMap<SkillKey, FrontendSkill>,
+) {
+    let emit = |event: &str, skill: &FrontendSkill| {
+        println!("🔔 {}: {}", event, skill.name);
+        if let Err(e) = app.emit(event, skill.clone()) {
+            eprintln!("⚠️ 无法发送事件 / Failed to emit {}: {}", event, e);
+        }
+    };
+
+    for (key, skill) in current.iter() {
+        match previous.get(key) {
+            None => emit(SKILL_ADDED_EVENT, skill),
+            Some(old) if old != skill => emit(SKILL_UPDATED_EVENT, skill),
+            Some(_) => {}
+        }
+    }
+
+    for (key, skill) in previous.iter() {
+        if !current.contains_key(key) {
+            emit(SKILL_REMOVED_EVENT, skill);
+        }
+    }
+}
+
+/// Whether a changed path can affect the skill list
+/// 变化的路径是否可能影响 skill 列表
+/// 路径必须位于某个目标之内，或是通往尚不存在的目标的上级目录
+/// The path must be inside a target, or on the way to a target that does not exist yet
+fn is_relevant(path: &Path, targets: &[PathBuf]) -> bool {
+    targets
+        .iter()
+        .any(|target| path.starts_with(target) || target.starts_with(path))
+        // 忽略 git 内部文件和编辑器临时文件 / Ignore git internals and editor swap files
+        && !path.components().any(|component| component.as_os_str() == ".git")
+        && !path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.ends_with('~') || name.ends_with(".swp"))
+}
+
+/// Watch skill directories and installed_plugins.json, emitting skill change events
+/// 监听 skills 目录和 installed_plugins.json，并发出 skill 变化事件
+pub fn start_skill_watcher<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    let (tx, rx) = mpsc::channel::<DebounceEventResult>();
+    let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, tx)
+        .map_err(|e| format!("无法创建文件监听器 / Failed to create file watcher: {}", e))?;
+
+    std::thread::spawn(move || {
+        let mut watched = HashSet::new();
+        let mut targets = update_watches(debouncer.watcher(), &mut watched);
+        println!("👀 正在监听 {} 个路径 / Watching {} paths", watched.len(), watched.len());
+
+        for result in rx {
+            let events = match result {
+                Ok(events) => events,
+                Err(e) => {
+                    eprintln!("⚠️ 文件监听错误 / File watcher error: {}", e);
+                    continue;
+                }
+            };
+
+            if !events.iter().any(|event| is_relevant(&event.path, &targets)) {
+                continue;
+            }
+
+            // 新建的 skills 目录或插件需要补充监听 / Newly created skills folders or plugins need watches
+            targets = update_watches(debouncer.watcher(), &mut watched);
+
+            // 与界面扫描并发时索引写入由 SkillIndex::save 串行化 / SkillIndex::save serialises index writes with concurrent UI scans
+            let current = by_key(scan_skills(&ScanContext::load()).skills);
+            let known = app.state::<KnownSkills>();
+            let Ok(mut known) = known.0.lock() else {
+                continue;
+            };
+            // 前端尚未扫描时无需发送事件，其首次扫描会得到最新结果
+            // Nothing to emit before the frontend's first scan, which will see the latest state anyway
+            if let Some(previous) = known.as_ref() {
+                emit_changes(&app, previous, &current);
+            }
+            *known = Some(current);
+        }
+    });
+
+    Ok(())
+}
@@ -6,6 +6,7 @@ import { useSkills } from "./hooks/useSkills";
 import { useKeyboardNavigation } from "./hooks/useKeyboardNavigation";
 import { useInputParser } from "./hooks/useInputParser";
 import { useSkillUsage } from "./hooks/useSkillUsage";
+import { SkillScanner, parseParameterValues } from "./services/skillScanner";
 import type { Skill } from "./types/skill";
 
 /**
@@ -23,6 +24,34 @@ function App() {
   const [toastMessage, setToastMessage] = useState<string | null>(null);
   const [toastVisible, setToastVisible] = useState(false);
 
+  // 拖放到窗口上的文件，用于 {selected_files} / Files dropped onto the window, used for {selected_files}
+  const [selectedFiles, setSelectedFiles] = useState<string[]>([]);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    let cancelled = false;
+    import("@tauri-apps/api/webview")
+      .then(({ getCurrentWebview }) =>
+        getCurrentWebview().onDragDropEvent((event) => {
+          if (event.payload.type === "drop") {
+            setSelectedFiles(event.payload.paths);
+          }
+        })
+      )
+      .then((stop) => {
+        if (cancelled) {
+          stop();
+        } else {
+          unlisten = stop;
+        }
+      })
+      .catch((error) => console.warn("无法监听文件拖放 / Failed to listen for file drops:", error));
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+
   // Input parser / 输入解析器
   const {
     rawInput,
@@ -48,9 +77,28 @@ function App() {
         return;
       }
 
-      // 准备复制到剪贴板 / Prepare clipboard content
+      // 准备复制到剪贴板，按所属代理的调用方式 / Prepare clipboard content using the agent's invocation syntax
       const skillName = skill.name.startsWith("/") ? skill.name.slice(1) : skill.name;
-      const content = `/${skillName}`;
+      let content = skill.invocation || `/${skillName}`;
+
+      // 声明了参数或命令模板时由后端校验并生成 / Validate and build in the backend when parameters or a template are declared
+      if (skill.parameters?.length || skill.commandTemplate) {
+        const scanner = new SkillScanner();
+        try {
+          if (skill.parameters?.length) {
+            const values = parseParameterValues(skill.parameters, parsedInput?.task);
+            content = await scanner.buildCommand(skill, values, parsedInput?.task, selectedFiles);
+          } else {
+            content = await scanner.renderCommand(skill.commandTemplate!, parsedInput?.task, selectedFiles);
+          }
+        } catch (error) {
+          console.error("生成命令失败 / Failed to build command:", error);
+          setToastMessage(String(error));
+          setToastVisible(true);
+          setTimeout(() => setToastVisible(false), 4000);
+          return;
+        }
+      }
 
       // 优先使用 navigator.clipboard / Prefer navigator.clipboard
       let copied = false;
@@ -94,7 +142,7 @@ function App() {
       setToastVisible(true);
       setTimeout(() => setToastVisible(false), 2000);
     },
-    [filteredSkills, recordUsage]
+    [filteredSkills, recordUsage, parsedInput, selectedFiles]
   );
 
   // Tab 自动补全功能 / Tab auto-complete feature
@@ -161,7 +209,10 @@ function App() {
               <Button
                 appearance="subtle"
                 icon={<Dismiss24Regular />}
-                onClick={() => clearInput()}
+                onClick={() => {
+                  clearInput();
+                  setSelectedFiles([]);
+                }}
                 size="small"
                 style={{ minWidth: "24px", padding: "0 4px" }}
               />
@@ -191,6 +242,13 @@ function App() {
         </div>
       )}
 
+      {/* Selected files / 选中的文件 */}
+      {selectedFiles.length > 0 && (
+        <div style={{ padding: "8px 12px", background: "#f0f0f0", borderRadius: "4px", fontSize: "12px" }}>
+          <Text size={200}>📎 {selectedFiles.length} 个文件 / files: {selectedFiles.join(" ")}</Text>
+        </div>
+      )}
+
       {/* Skill list / Skill 列表 */}
       {loading ? (
         <div className="loading">
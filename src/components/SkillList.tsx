@@ -163,6 +163,46 @@ function getSkillEmoji(skillName: string): string {
   return "✨";
 }
 
+/**
+ * Tag 显示文本 / Tag label text
+ */
+const SKILL_TAG_LABELS: Record<string, string> = {
+  project: "PROJ",
+  user: "USER",
+  plugin: "PLUGIN",
+  managed: "MANAGED",
+  codex: "CODEX",
+};
+
+/**
+ * 条目类型显示文本，skill 不显示 / Item kind label text, skills show none
+ */
+const SKILL_KIND_LABELS: Record<string, string> = {
+  command: "CMD",
+  agent: "AGENT",
+};
+
+/**
+ * 来源提示文本，推测的来源会明确标注，官方 skill 附带匹配的规则
+ * Provenance tooltip, guessed origins are flagged explicitly and official skills show the matched rule
+ */
+function getProvenanceTitle(skill: Skill): string | undefined {
+  const lines: string[] = [];
+  const plugin = skill.provenance?.plugin;
+  if (plugin) {
+    const guessed = skill.provenance?.source === "guessed" ? " (guessed)" : "";
+    lines.push(`${plugin.id} ${plugin.version}${guessed}`, `scope: ${plugin.scope}`);
+    if (plugin.gitCommitSha) lines.push(`commit: ${plugin.gitCommitSha.slice(0, 12)}`);
+    if (plugin.installedAt) lines.push(`installed: ${plugin.installedAt}`);
+    if (plugin.lastUpdated) lines.push(`updated: ${plugin.lastUpdated}`);
+  }
+  if (skill.official) {
+    lines.push(`官方 / Official: ${skill.official.label}`);
+    if (skill.official.verified) lines.push("内容哈希已验证 / Content hash verified");
+  }
+  return lines.length > 0 ? lines.join("\n") : undefined;
+}
+
 interface SkillListProps {
   /** List of skills to display / 要显示的 Skill 列表 */
   skills: Skill[];
@@ -213,8 +253,26 @@ export function SkillList({ skills, selectedIndex, onSkillClick, isRecentUsed }:
                 </div>
                 {/* Tag 标签 / Tag label */}
                 {skill.tag && (
-                  <div className={`skill-tag ${skill.tag}`}>
-                    {skill.tag === "project" ? "PROJ" : "USER"}
+                  <div
+                    className={`skill-tag ${skill.tag}`}
+                    title={getProvenanceTitle(skill)}
+                  >
+                    {SKILL_TAG_LABELS[skill.tag] ?? skill.tag.toUpperCase()}
+                  </div>
+                )}
+                {/* 命令 / 子代理类型标签 / Command or subagent kind label */}
+                {skill.kind && SKILL_KIND_LABELS[skill.kind] && (
+                  <div className={`skill-tag ${skill.kind}`}>
+                    {SKILL_KIND_LABELS[skill.kind]}
+                  </div>
+                )}
+                {/* 覆盖同名 skill 标签 / Overrides same-name skill label */}
+                {skill.overrides && skill.overrides.length > 0 && (
+                  <div
+                    className="skill-tag override"
+                    title={skill.overrides.map((o) => `overrides ${o.tag} skill ${skill.name}: ${o.path}`).join("\n")}
+                  >
+                    覆盖 {skill.overrides.map((o) => o.tag.toUpperCase()).join("/")}
                   </div>
                 )}
                 {/* 最近使用标签 / Recent usage label */}
@@ -1,6 +1,18 @@
 import { useState, useEffect } from "react";
 import type { Skill } from "../types/skill";
-import { SkillScanner } from "../services/skillScanner";
+import { SkillScanner, toSkill, type RawSkill } from "../services/skillScanner";
+
+/**
+ * 命令或 Codex skill 可以与 Claude skill 同名，因此按类型、代理和名称匹配
+ * Commands or Codex skills may share a name with a Claude skill, so match on kind, agent and name
+ */
+function isSameSkill(a: Skill, b: Skill): boolean {
+  return (
+    a.name === b.name &&
+    (a.kind ?? "skill") === (b.kind ?? "skill") &&
+    (a.agent ?? "claude") === (b.agent ?? "claude")
+  );
+}
 
 /**
  * Hook for loading and managing skills
@@ -46,7 +58,13 @@ export function useSkills() {
         console.log("🔍 开始扫描真实 skills / Scanning real skills...");
 
         const scanner = new SkillScanner();
-        const scannedSkills = await scanner.scanSkills();
+        // 逐目录显示扫描到的 skills / Show skills as each directory finishes scanning
+        const streamed: Skill[] = [];
+        const scannedSkills = await scanner.scanSkills(false, (batch) => {
+          if (!mounted || batch.length === 0) return;
+          streamed.push(...batch);
+          setSkills([...streamed]);
+        });
 
         if (mounted && scannedSkills.length > 0) {
           console.log(`✅ 成功加载 ${scannedSkills.length} 个 skills / Successfully loaded ${scannedSkills.length} skills`);
@@ -68,8 +86,40 @@ export function useSkills() {
 
     loadSkills();
 
+    // 监听后端文件变化事件，实时更新列表 / Listen for backend file change events to update the list live
+    const unlisteners: Array<() => void> = [];
+    const watchSkills = async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+        const upsert = (event: { payload: RawSkill }) => {
+          const skill = toSkill(event.payload);
+          setSkills((prev) => {
+            const index = prev.findIndex((s) => isSameSkill(s, skill));
+            if (index === -1) return [...prev, skill];
+            const next = [...prev];
+            next[index] = skill;
+            return next;
+          });
+        };
+        const remove = (event: { payload: RawSkill }) => {
+          const skill = toSkill(event.payload);
+          setSkills((prev) => prev.filter((s) => !isSameSkill(s, skill)));
+        };
+
+        unlisteners.push(await listen<RawSkill>("skill-added", upsert));
+        unlisteners.push(await listen<RawSkill>("skill-updated", upsert));
+        unlisteners.push(await listen<RawSkill>("skill-removed", remove));
+        if (!mounted) unlisteners.forEach((unlisten) => unlisten());
+      } catch (err) {
+        console.warn("⚠️ 无法监听 skill 变化 / Failed to listen for skill changes:", err);
+      }
+    };
+
+    watchSkills();
+
     return () => {
       mounted = false;
+      unlisteners.forEach((unlisten) => unlisten());
     };
   }, []);
 
@@ -0,0 +1,21 @@
+import type { Marketplace } from "../types/marketplace";
+
+/**
+ * Marketplace 目录服务 - 从 Rust 后端读取本地克隆的 marketplaces
+ * Marketplace catalog service - Read locally cloned marketplaces from Rust backend
+ */
+export class MarketplaceService {
+  /**
+   * 列出所有 marketplace 及其提供的插件和 skills
+   * List every marketplace with the plugins and skills it offers
+   */
+  async listMarketplaces(): Promise<Marketplace[]> {
+    try {
+      const { invoke } = await import("@tauri-apps/api/core");
+      return await invoke<Marketplace[]>("list_marketplaces");
+    } catch (error) {
+      console.error("读取 marketplaces 失败 / Failed to list marketplaces:", error);
+      return [];
+    }
+  }
+}
@@ -0,0 +1,21 @@
+import type { McpServerList } from "../types/mcp";
+
+/**
+ * MCP server 服务 - 从 Rust 后端读取当前项目可用的 MCP servers
+ * MCP server service - Read the MCP servers available in the current project from Rust backend
+ */
+export class McpService {
+  /**
+   * 列出当前项目可用的 MCP servers
+   * List the MCP servers available in the current project
+   */
+  async listMcpServers(): Promise<McpServerList> {
+    try {
+      const { invoke } = await import("@tauri-apps/api/core");
+      return await invoke<McpServerList>("list_mcp_servers");
+    } catch (error) {
+      console.error("读取 MCP servers 失败 / Failed to list MCP servers:", error);
+      return { servers: [], errors: [] };
+    }
+  }
+}
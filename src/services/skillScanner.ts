@@ -1,4 +1,119 @@
-import type { Skill } from "../types/skill";
+import type { OfficialStatus, OverriddenSkill, Skill, SkillKind, SkillParameter, SkillProvenance } from "../types/skill";
+
+/**
+ * Rust 后端返回的 skill
+ * Skill as returned by the Rust backend
+ */
+export interface RawSkill {
+  name: string;
+  namespace?: string;
+  display_name: string;
+  description: string;
+  category: string;
+  marketplace: string;
+  tag: string;
+  kind: SkillKind;
+  agent: string;
+  path: string;
+  command: string;
+  invocation: string;
+  license?: string;
+  version?: string;
+  allowedTools: string[];
+  model?: string;
+  argumentHint?: string;
+  commandTemplate?: string;
+  parameters?: SkillParameter[];
+  tags: string[];
+  extra: Record<string, unknown>;
+  overrides: OverriddenSkill[];
+  provenance: SkillProvenance;
+  official?: OfficialStatus;
+}
+
+/**
+ * 转换为前端的 Skill 类型
+ * Convert a backend skill to the frontend Skill type
+ */
+export function toSkill(fs: RawSkill): Skill {
+  return {
+    name: fs.name,
+    namespace: fs.namespace,
+    displayName: fs.display_name,
+    description: fs.description,
+    category: fs.category,
+    marketplace: fs.marketplace,
+    tag: fs.tag,
+    kind: fs.kind,
+    agent: fs.agent,
+    path: fs.path,
+    command: fs.command,
+    invocation: fs.invocation,
+    license: fs.license,
+    version: fs.version,
+    allowedTools: fs.allowedTools,
+    model: fs.model,
+    argumentHint: fs.argumentHint,
+    commandTemplate: fs.commandTemplate,
+    parameters: fs.parameters,
+    tags: fs.tags,
+    extra: fs.extra,
+    overrides: fs.overrides,
+    provenance: fs.provenance,
+    official: fs.official,
+  };
+}
+
+/**
+ * 收集模板所需的前端上下文，仅在模板需要时读取剪贴板和选中的文件
+ * Collect frontend context for a template, reading the clipboard and selected files only when the template needs them
+ */
+async function templateInput(template: string, task?: string, selectedFiles: string[] = []) {
+  let clipboard: string | undefined;
+  if (template.includes("{clipboard}")) {
+    clipboard = await navigator.clipboard?.readText().catch(() => undefined);
+  }
+  return {
+    task,
+    clipboard,
+    selectedFiles: template.includes("{selected_files}") ? selectedFiles : [],
+  };
+}
+
+/**
+ * 将输入框中 skill 名称后的文本解析为参数值
+ * Parse the text typed after the skill name into parameter values
+ * 支持 key=value，其余按声明顺序作为位置参数 / Accepts key=value, other tokens fill parameters in declared order
+ */
+export function parseParameterValues(parameters: SkillParameter[], text?: string): Record<string, string> {
+  const values: Record<string, string> = {};
+  const positional = parameters.map((p) => p.name);
+
+  for (const token of text?.match(/"[^"]*"|\S+/g) ?? []) {
+    const match = token.match(/^([\w-]+)=(.*)$/);
+    if (match && parameters.some((p) => p.name === match[1])) {
+      values[match[1]] = match[2].replace(/^"|"$/g, "");
+      continue;
+    }
+    const name = positional.find((n) => !(n in values));
+    // 多余的位置参数保留给后端报告 / Leave extra positional tokens for the backend to report
+    values[name ?? token] = token.replace(/^"|"$/g, "");
+  }
+
+  return values;
+}
+
+/**
+ * 扫描进度，每扫描完一个目录发送一次
+ * Scan progress, sent once per scanned directory
+ */
+export interface ScanProgress {
+  directory: string;
+  directoriesScanned: number;
+  directoriesTotal: number;
+  skillCount: number;
+  skills: RawSkill[];
+}
 
 /**
  * Skill 扫描器 - 从 Rust 后端获取 skills
@@ -8,38 +123,90 @@ export class SkillScanner {
   /**
    * 扫描 skills 目录并解析所有 SKILL.md 文件
    * Scan skills directory and parse all SKILL.md files
+   * @param rebuild 丢弃索引并重新解析所有 skills / Discard the index and re-parse every skill
+   * @param onProgress 每扫描完一个目录时收到该目录新增的 skills / Receives the skills added by each scanned directory
    */
-  async scanSkills(): Promise<Skill[]> {
+  async scanSkills(
+    rebuild = false,
+    onProgress?: (skills: Skill[], progress: ScanProgress) => void
+  ): Promise<Skill[]> {
+    let unlisten: (() => void) | undefined;
     try {
       // 调用 Rust 后端命令扫描 skills / Call Rust backend command to scan skills
       const { invoke } = await import("@tauri-apps/api/core");
-      const frontendSkills = await invoke<
-        Array<{
+      if (onProgress) {
+        const { listen } = await import("@tauri-apps/api/event");
+        unlisten = await listen<ScanProgress>("skill-scan-progress", (event) => {
+          onProgress(event.payload.skills.map(toSkill), event.payload);
+        });
+      }
+
+      const result = await invoke<{
+        skills: RawSkill[];
+        errors: Array<{ path: string; message: string }>;
+        shadowed: Array<{
           name: string;
-          display_name: string;
-          description: string;
-          category: string;
-          marketplace: string;
-          tag: string;
           path: string;
-          command: string;
-        }>
-      >("scan_skills_directory");
+          tag: string;
+          shadowedByPath: string;
+          shadowedByTag: string;
+        }>;
+      }>(rebuild ? "rebuild_index" : "scan_skills_directory");
+
+      // 部分目录无法读取时仍显示其余 skills / Keep showing other skills when some paths fail
+      for (const err of result.errors) {
+        console.warn(`⚠️ 无法扫描 / Failed to scan ${err.path}: ${err.message}`);
+      }
+      for (const item of result.shadowed) {
+        console.info(`ℹ️ ${item.tag} skill "${item.name}" (${item.path}) 被覆盖 / is overridden by ${item.shadowedByPath}`);
+      }
 
       // 转换为前端的 Skill 类型 / Convert to frontend Skill type
-      return frontendSkills.map((fs) => ({
-        name: fs.name,
-        displayName: fs.display_name,
-        description: fs.description,
-        category: fs.category,
-        marketplace: fs.marketplace,
-        tag: fs.tag,
-        path: fs.path,
-        command: fs.command,
-      }));
+      return result.skills.map(toSkill);
     } catch (error) {
       console.error("扫描 skills 目录失败 / Failed to scan skills directory:", error);
       return []; // 返回空数组 / Return empty array
+    } finally {
+      unlisten?.();
     }
   }
+
+  /**
+   * 渲染 skill 的命令模板，占位符未知或缺少值时抛出错误
+   * Render a skill's command template, throwing on unknown or missing placeholders
+   * @param template 命令模板 / Command template
+   * @param task 用户在 skill 名称后输入的任务 / Task typed after the skill name
+   * @param selectedFiles 拖放到窗口上的文件 / Files dropped onto the window
+   */
+  async renderCommand(template: string, task?: string, selectedFiles: string[] = []): Promise<string> {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return await invoke<string>("render_skill_command", {
+      template,
+      input: await templateInput(template, task, selectedFiles),
+    });
+  }
+
+  /**
+   * 校验参数并生成最终命令，参数无效时抛出错误（每行一个问题）
+   * Validate parameters and build the final command, throwing one problem per line when invalid
+   * @param skill 声明了参数的 skill / Skill declaring parameters
+   * @param values 参数名到输入值 / Parameter name to input value
+   * @param task 用户在 skill 名称后输入的任务 / Task typed after the skill name
+   * @param selectedFiles 拖放到窗口上的文件 / Files dropped onto the window
+   */
+  async buildCommand(
+    skill: Skill,
+    values: Record<string, string>,
+    task?: string,
+    selectedFiles: string[] = []
+  ): Promise<string> {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return await invoke<string>("build_skill_command", {
+      invocation: skill.invocation || `/${skill.name}`,
+      // 后端从该路径重新读取参数和模板 / The backend re-reads parameters and the template from this path
+      path: skill.path,
+      values,
+      input: await templateInput(skill.commandTemplate ?? "", task, selectedFiles),
+    });
+  }
 }
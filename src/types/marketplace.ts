@@ -0,0 +1,56 @@
+/**
+ * Skill offered by a marketplace plugin
+ * Marketplace 插件提供的 skill
+ */
+export interface MarketplaceSkill {
+  /** Skill name / Skill 名称 */
+  name: string;
+  /** Skill description / Skill 描述 */
+  description: string;
+  /** Skill directory in the local clone / 本地克隆中的 skill 目录 */
+  path: string;
+}
+
+/**
+ * Plugin offered by a marketplace
+ * Marketplace 提供的插件
+ */
+export interface MarketplacePlugin {
+  /** Full plugin id "plugin@marketplace" / 插件完整 ID */
+  id: string;
+  /** Plugin name / 插件名称 */
+  name: string;
+  /** Plugin description / 插件描述 */
+  description: string;
+  /** Version offered by the marketplace / Marketplace 提供的版本 */
+  version?: string;
+  /** Whether the plugin is installed / 是否已安装 */
+  installed: boolean;
+  /** Installed version / 已安装版本 */
+  installedVersion?: string;
+  /** Skills bundled in the plugin / 插件自带的 skills */
+  skills: MarketplaceSkill[];
+}
+
+/**
+ * Locally cloned marketplace
+ * 本地克隆的 marketplace
+ */
+export interface Marketplace {
+  /** Marketplace id / Marketplace ID */
+  id: string;
+  /** Display name / 显示名称 */
+  name: string;
+  /** Owner name / 所有者 */
+  owner?: string;
+  /** Source repo or URL / 来源仓库或 URL */
+  source?: string;
+  /** Local clone path / 本地克隆路径 */
+  installLocation: string;
+  /** Last update time / 最后更新时间 */
+  lastUpdated?: string;
+  /** Offered plugins / 提供的插件 */
+  plugins: MarketplacePlugin[];
+  /** Error reading the manifest / 读取清单时的错误 */
+  error?: string;
+}
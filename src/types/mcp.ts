@@ -0,0 +1,27 @@
+/**
+ * MCP server available in the current project
+ * 当前项目可用的 MCP server
+ */
+export interface McpServer {
+  /** Server name / Server 名称 */
+  name: string;
+  /** Transport / 传输方式 */
+  transport: "stdio" | "sse" | "http" | string;
+  /** Full command line of a stdio server / stdio server 的完整命令行 */
+  command?: string;
+  /** URL of a remote server / 远程 server 的地址 */
+  url?: string;
+  /** Scope: "local", "project" or "user" / 范围 */
+  scope: string;
+  /** Config file declaring the server / 声明该 server 的配置文件 */
+  source: string;
+}
+
+/**
+ * MCP servers and the config files that could not be read
+ * MCP server 列表以及无法读取的配置文件
+ */
+export interface McpServerList {
+  servers: McpServer[];
+  errors: { path: string; message: string }[];
+}
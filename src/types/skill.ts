@@ -5,22 +5,139 @@
 export interface Skill {
   /** Skill unique identifier / Skill 唯一标识符 */
   name: string;
+  /** Intermediate folders of a nested skill / 嵌套 skill 的中间目录 */
+  namespace?: string;
   /** Skill display name / Skill 显示名称 */
   displayName?: string;
   /** Skill description / Skill 描述 */
   description?: string;
   /** Skill command to execute / 要执行的命令 */
   command?: string;
+  /** Text typed inside the agent session, e.g. "/pdf" or "$pdf" / 在代理会话中输入的文本 */
+  invocation?: string;
+  /** Agent profile id, e.g. "claude" or "codex" / 所属代理配置 ID */
+  agent?: string;
   /** Skill category / Skill 分类 */
   category?: string;
   /** Skill marketplace source / Skill 来源市场 */
   marketplace?: string;
-  /** Tag: "project" 项目技能, "user" 用户技能 */
+  /** Tag: "managed" 组织托管技能, "project" 项目技能, "user" 用户技能, "plugin" 插件技能 */
   tag?: string;
+  /** Item kind: "skill", "command" 斜杠命令, "agent" 子代理 */
+  kind?: SkillKind;
   /** Skill icon / Skill 图标 */
   icon?: string;
   /** Skill shortcut key / Skill 快捷键 */
   shortcut?: string;
+  /** Skill directory path / Skill 目录路径 */
+  path?: string;
+  /** Declared license / 声明的许可证 */
+  license?: string;
+  /** Declared version / 声明的版本 */
+  version?: string;
+  /** Tools the skill may use / Skill 允许使用的工具 */
+  allowedTools?: string[];
+  /** Model the skill targets / Skill 指定的模型 */
+  model?: string;
+  /** Argument hint / 参数提示 */
+  argumentHint?: string;
+  /** Command template with {task}, {git_branch}... placeholders / 带占位符的命令模板 */
+  commandTemplate?: string;
+  /** Declared named parameters, used to render a form / 声明的命名参数，用于生成表单 */
+  parameters?: SkillParameter[];
+  /** Skill tags / Skill 标签 */
+  tags?: string[];
+  /** Unrecognised front matter keys / 未识别的 Front Matter 字段 */
+  extra?: Record<string, unknown>;
+  /** Same-name skills hidden by this one / 被当前 skill 覆盖的同名 skills */
+  overrides?: OverriddenSkill[];
+  /** Where the skill came from / Skill 来源 */
+  provenance?: SkillProvenance;
+  /** Official status and the rule that matched / 官方 skill 判定结果 */
+  official?: OfficialStatus;
+}
+
+/**
+ * Kind of launcher item
+ * 启动器条目类型
+ */
+export type SkillKind = "skill" | "command" | "agent";
+
+/**
+ * Named parameter declared in SKILL.md front matter
+ * SKILL.md Front Matter 中声明的命名参数
+ */
+export interface SkillParameter {
+  /** Parameter name / 参数名 */
+  name: string;
+  /** Value type / 值类型 */
+  type: "string" | "number" | "integer" | "boolean" | "path";
+  /** Whether a value is required / 是否必填 */
+  required: boolean;
+  /** Allowed choices / 可选值 */
+  enum?: string[];
+  /** Default value / 默认值 */
+  default?: string;
+  /** Description / 说明 */
+  description?: string;
+}
+
+/**
+ * Official status of a skill and why
+ * Skill 的官方状态及原因
+ */
+export interface OfficialStatus {
+  /** Matched rule / 匹配的规则 */
+  rule: "marketplace" | "source" | "hash";
+  /** Explanation of the rule / 规则说明 */
+  label: string;
+  /** Content hash is on the allow-list / 内容哈希在白名单中 */
+  verified: boolean;
+}
+
+/**
+ * Where a skill came from and how that was determined
+ * Skill 来源及判定方式
+ */
+export interface SkillProvenance {
+  /** "plugin": inside a plugin installPath, "guessed": heuristic match, "local": not from a plugin */
+  source: "plugin" | "guessed" | "local";
+  /** Owning plugin / 所属插件 */
+  plugin?: PluginRef;
+}
+
+/**
+ * Plugin a skill was loaded from
+ * Skill 所属的插件
+ */
+export interface PluginRef {
+  /** Full plugin id "plugin@marketplace" / 插件完整 ID */
+  id: string;
+  /** Plugin namespace / 插件命名空间 */
+  name: string;
+  /** Installed version / 已安装版本 */
+  version: string;
+  /** Marketplace id / Marketplace ID */
+  marketplaceId: string;
+  /** Install scope / 安装范围 */
+  scope: string;
+  /** Installed git commit / 已安装的 git 提交 */
+  gitCommitSha?: string;
+  /** Install time / 安装时间 */
+  installedAt?: string;
+  /** Last update time / 最后更新时间 */
+  lastUpdated?: string;
+}
+
+/**
+ * A same-name skill hidden by a higher-priority one
+ * 被更高优先级同名 skill 覆盖的 skill
+ */
+export interface OverriddenSkill {
+  /** Skill directory path / Skill 目录路径 */
+  path: string;
+  /** Tag of the hidden skill / 被覆盖 skill 的 tag */
+  tag: string;
 }
 
 /**